@@ -55,8 +55,8 @@ mod tests {
             text: "food".to_string(),
         }
         .into();
-        let wrapper = QuerierWrapper::new(&deps.querier);
-        let response: SpecialResponse = wrapper.custom_query(&req).unwrap();
+        let wrapper: QuerierWrapper<SpecialQuery> = QuerierWrapper::new(&deps.querier);
+        let response: SpecialResponse = wrapper.query(&req).unwrap();
         assert_eq!(response.msg, "FOOD");
     }
 }
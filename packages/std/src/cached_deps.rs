@@ -0,0 +1,98 @@
+use serde::de::DeserializeOwned;
+use std::any::type_name;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::deps::Deps;
+use crate::errors::{StdError, StdResult};
+use crate::query::CustomQuery;
+use crate::results::Empty;
+use crate::serde::from_slice;
+
+/// CachedDeps wraps `Deps` and memoizes storage reads made through `load` and `may_load`.
+/// Loading the same key again deserializes the cached bytes without reading from storage.
+/// Missing keys are cached as well.
+///
+/// The cache never sees writes, so it is only safe to use within a single query or
+/// message. Create it via `Deps::cached` and drop it at the end.
+pub struct CachedDeps<'a, C: CustomQuery = Empty> {
+    pub deps: Deps<'a, C>,
+    cache: RefCell<HashMap<Vec<u8>, Option<Vec<u8>>>>,
+}
+
+impl<'a, C: CustomQuery> CachedDeps<'a, C> {
+    pub fn new(deps: Deps<'a, C>) -> Self {
+        CachedDeps {
+            deps,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Loads and parses the value stored under `key`, returning an error if it does not exist
+    pub fn load<T: DeserializeOwned>(&self, key: &[u8]) -> StdResult<T> {
+        self.may_load(key)?
+            .ok_or_else(|| StdError::not_found(type_name::<T>()))
+    }
+
+    /// Loads and parses the value stored under `key`, returning `None` if it does not exist
+    pub fn may_load<T: DeserializeOwned>(&self, key: &[u8]) -> StdResult<Option<T>> {
+        match self.raw_get(key) {
+            Some(value) => from_slice(&value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn raw_get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        if let Some(cached) = self.cache.borrow().get(key) {
+            return cached.clone();
+        }
+        let value = self.deps.storage.get(key);
+        self.cache.borrow_mut().insert(key.to_vec(), value.clone());
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{mock_dependencies, MockStorage, StorageOp};
+    use crate::{to_vec, Storage};
+
+    #[test]
+    fn load_reads_storage_once() {
+        let mut deps = mock_dependencies(&[]).map_storage(|_| MockStorage::with_recording());
+        deps.storage.set(b"config", &to_vec(&42u32).unwrap());
+
+        let cached = deps.as_ref().cached();
+        assert_eq!(cached.load::<u32>(b"config").unwrap(), 42);
+        assert_eq!(cached.load::<u32>(b"config").unwrap(), 42);
+        assert_eq!(cached.may_load::<u32>(b"config").unwrap(), Some(42));
+        // missing keys are cached as well
+        assert_eq!(cached.may_load::<u32>(b"missing").unwrap(), None);
+        match cached.load::<u32>(b"missing").unwrap_err() {
+            StdError::NotFound { kind, .. } => assert_eq!(kind, "u32"),
+            err => panic!("Unexpected error: {:?}", err),
+        }
+        drop(cached);
+
+        let gets = deps
+            .storage
+            .access_log()
+            .iter()
+            .filter(|op| matches!(op, StorageOp::Get { .. }))
+            .count();
+        assert_eq!(gets, 2);
+    }
+
+    #[test]
+    fn load_returns_parse_errors() {
+        let mut deps = mock_dependencies(&[]);
+        deps.storage.set(b"config", b"not json");
+
+        let cached = deps.as_ref().cached();
+        match cached.load::<u32>(b"config").unwrap_err() {
+            StdError::ParseErr { target_type, .. } => assert_eq!(target_type, "u32"),
+            err => panic!("Unexpected error: {:?}", err),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::traits::{Querier, QuerierResult};
+
+/// CachingQuerier memoizes the results of another querier by request bytes.
+/// A second identical query returns the cached result without reaching the
+/// wrapped querier.
+///
+/// The cache is never invalidated. Create it explicitly for the duration of one message
+/// and drop it at the end, since results must not be reused across messages.
+/// Use it via `Deps::with_querier` or `DepsMut::with_querier`.
+pub struct CachingQuerier<'a> {
+    querier: &'a dyn Querier,
+    cache: RefCell<HashMap<Vec<u8>, QuerierResult>>,
+}
+
+impl<'a> CachingQuerier<'a> {
+    pub fn new(querier: &'a dyn Querier) -> Self {
+        CachingQuerier {
+            querier,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl<'a> Querier for CachingQuerier<'a> {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        if let Some(cached) = self.cache.borrow().get(bin_request) {
+            return cached.clone();
+        }
+        let result = self.querier.raw_query(bin_request);
+        self.cache
+            .borrow_mut()
+            .insert(bin_request.to_vec(), result.clone());
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{mock_dependencies, MockQuerier};
+    use crate::{coin, coins, Empty, QuerierWrapper};
+    use std::cell::Cell;
+
+    /// A querier counting the calls to `raw_query`
+    struct CountingQuerier {
+        querier: MockQuerier,
+        queries: Cell<u32>,
+    }
+
+    impl Querier for CountingQuerier {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            self.queries.set(self.queries.get() + 1);
+            self.querier.raw_query(bin_request)
+        }
+    }
+
+    #[test]
+    fn identical_queries_hit_inner_querier_once() {
+        let counting = CountingQuerier {
+            querier: MockQuerier::new(&[("alice", &coins(5, "ATOM"))]),
+            queries: Cell::new(0),
+        };
+        let caching = CachingQuerier::new(&counting);
+        let deps = mock_dependencies(&[]);
+        let deps = deps.as_ref();
+        let deps = deps.with_querier(&caching);
+
+        let balance = deps.querier.query_balance("alice", "ATOM").unwrap();
+        assert_eq!(balance, coin(5, "ATOM"));
+        let balance = deps.querier.query_balance("alice", "ATOM").unwrap();
+        assert_eq!(balance, coin(5, "ATOM"));
+        assert_eq!(counting.queries.get(), 1);
+
+        // a different request is not cached yet
+        let balance = deps.querier.query_balance("bob", "ATOM").unwrap();
+        assert_eq!(balance, coin(0, "ATOM"));
+        assert_eq!(counting.queries.get(), 2);
+    }
+
+    #[test]
+    fn errors_are_cached_too() {
+        let counting = CountingQuerier {
+            querier: MockQuerier::new(&[]),
+            queries: Cell::new(0),
+        };
+        let caching = CachingQuerier::new(&counting);
+        let wrapper = QuerierWrapper::<Empty>::new(&caching);
+
+        let first = wrapper.query_wasm_smart::<Empty>("nothing", &Empty {});
+        let second = wrapper.query_wasm_smart::<Empty>("nothing", &Empty {});
+        assert!(first.is_err());
+        assert_eq!(first, second);
+        assert_eq!(counting.queries.get(), 1);
+    }
+}
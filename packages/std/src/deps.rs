@@ -1,3 +1,8 @@
+use crate::addresses::Addr;
+use crate::cached_deps::CachedDeps;
+use crate::errors::{StdError, StdResult};
+use crate::query::CustomQuery;
+use crate::results::Empty;
 use crate::traits::{Api, Querier, Storage};
 use crate::QuerierWrapper;
 
@@ -11,17 +16,31 @@ pub struct OwnedDeps<S: Storage, A: Api, Q: Querier> {
     pub querier: Q,
 }
 
-pub struct DepsMut<'a> {
+/// Mutable access to the dependencies of the contract.
+///
+/// `C` is the custom query type supported by `querier`. It defaults to `Empty`.
+pub struct DepsMut<'a, C: CustomQuery = Empty> {
     pub storage: &'a mut dyn Storage,
     pub api: &'a dyn Api,
-    pub querier: QuerierWrapper<'a>,
+    pub querier: QuerierWrapper<'a, C>,
 }
 
-#[derive(Copy, Clone)]
-pub struct Deps<'a> {
+/// Read-only access to the dependencies of the contract.
+///
+/// `C` is the custom query type supported by `querier`. It defaults to `Empty`.
+pub struct Deps<'a, C: CustomQuery = Empty> {
     pub storage: &'a dyn Storage,
     pub api: &'a dyn Api,
-    pub querier: QuerierWrapper<'a>,
+    pub querier: QuerierWrapper<'a, C>,
+}
+
+// Use custom implementations of Copy and Clone, as `derive` would require `C` to implement them.
+impl<'a, C: CustomQuery> Copy for Deps<'a, C> {}
+
+impl<'a, C: CustomQuery> Clone for Deps<'a, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
 impl<S: Storage, A: Api, Q: Querier> OwnedDeps<S, A, Q> {
@@ -40,10 +59,30 @@ impl<S: Storage, A: Api, Q: Querier> OwnedDeps<S, A, Q> {
             querier: QuerierWrapper::new(&self.querier),
         }
     }
+
+    /// Replaces the querier by mapping the current one through `f`, keeping storage and api.
+    /// This allows wrapping the querier of `mock_dependencies` in a custom querier.
+    pub fn change_querier<Q2: Querier, F: FnOnce(Q) -> Q2>(self, f: F) -> OwnedDeps<S, A, Q2> {
+        OwnedDeps {
+            storage: self.storage,
+            api: self.api,
+            querier: f(self.querier),
+        }
+    }
+
+    /// Replaces the storage by mapping the current one through `f`, keeping api and querier.
+    /// This allows wrapping the storage of `mock_dependencies` in an instrumented storage.
+    pub fn map_storage<S2: Storage, F: FnOnce(S) -> S2>(self, f: F) -> OwnedDeps<S2, A, Q> {
+        OwnedDeps {
+            storage: f(self.storage),
+            api: self.api,
+            querier: self.querier,
+        }
+    }
 }
 
-impl<'a> DepsMut<'a> {
-    pub fn as_ref(&'_ self) -> Deps<'_> {
+impl<'a, C: CustomQuery> DepsMut<'a, C> {
+    pub fn as_ref(&'_ self) -> Deps<'_, C> {
         Deps {
             storage: self.storage,
             api: self.api,
@@ -51,19 +90,120 @@ impl<'a> DepsMut<'a> {
         }
     }
 
-    pub fn branch(&'_ mut self) -> DepsMut<'_> {
+    /// Calls `f` with a read-only view of the dependencies. The immutable borrow ends
+    /// when `f` returns, so the dependencies can be mutated again afterwards.
+    pub fn as_ref_scoped<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(Deps<'_, C>) -> R,
+    {
+        f(self.as_ref())
+    }
+
+    pub fn branch(&'_ mut self) -> DepsMut<'_, C> {
         DepsMut {
             storage: self.storage,
             api: self.api,
             querier: self.querier,
         }
     }
+
+    /// Returns a new borrow with the same storage and api, but with `querier` as the querier.
+    /// This is useful for stubbing out queries in tests.
+    pub fn with_querier<'b>(&'b mut self, querier: &'b dyn Querier) -> DepsMut<'b, C> {
+        DepsMut {
+            storage: self.storage,
+            api: self.api,
+            querier: QuerierWrapper::new(querier),
+        }
+    }
+
+    /// Converts into dependencies that only support queries without a custom type.
+    /// This allows passing them to helpers that take `DepsMut<'a>`.
+    pub fn into_empty(self) -> DepsMut<'a, Empty> {
+        DepsMut {
+            storage: self.storage,
+            api: self.api,
+            querier: self.querier.into_empty(),
+        }
+    }
+}
+
+impl<'a, C: CustomQuery> Deps<'a, C> {
+    /// Validates all addresses using `Api::addr_validate`, preserving their order.
+    /// Fails on the first invalid address. The error keeps the variant returned by
+    /// `addr_validate`, with the invalid address prepended to its message.
+    pub fn validate_addresses(&self, addrs: &[String]) -> StdResult<Vec<Addr>> {
+        addrs
+            .iter()
+            .map(|addr| {
+                self.api
+                    .addr_validate(addr)
+                    .map_err(|err| prepend_to_msg(err, addr))
+            })
+            .collect()
+    }
+
+    /// Returns a new borrow with the same storage and api, but with `querier` as the querier.
+    /// This is useful for stubbing out queries in tests.
+    pub fn with_querier<'b>(&'b self, querier: &'b dyn Querier) -> Deps<'b, C> {
+        Deps {
+            storage: self.storage,
+            api: self.api,
+            querier: QuerierWrapper::new(querier),
+        }
+    }
+
+    /// Returns a wrapper caching storage reads. See `CachedDeps` for when this is safe to use.
+    pub fn cached(self) -> CachedDeps<'a, C> {
+        CachedDeps::new(self)
+    }
+
+    /// Consumes the dependencies and returns a querier for the custom query type `Q`.
+    /// This allows choosing the custom query type at the call site.
+    pub fn into_querier<Q: CustomQuery>(self) -> QuerierWrapper<'a, Q> {
+        self.querier.into_custom()
+    }
+
+    /// Converts into dependencies that only support queries without a custom type.
+    /// This allows passing them to helpers that take `Deps<'a>`.
+    pub fn into_empty(self) -> Deps<'a, Empty> {
+        Deps {
+            storage: self.storage,
+            api: self.api,
+            querier: self.querier.into_empty(),
+        }
+    }
+}
+
+/// Rebuilds errors carrying a message with `prefix` prepended to it. Errors without a
+/// message are returned unchanged.
+fn prepend_to_msg(err: StdError, prefix: &str) -> StdError {
+    match err {
+        StdError::GenericErr { msg, .. } => StdError::generic_err(format!("{}: {}", prefix, msg)),
+        StdError::InvalidBase64 { msg, .. } => {
+            StdError::invalid_base64(format!("{}: {}", prefix, msg))
+        }
+        StdError::InvalidUtf8 { msg, .. } => StdError::invalid_utf8(format!("{}: {}", prefix, msg)),
+        StdError::ParseErr {
+            target_type, msg, ..
+        } => StdError::parse_err(target_type, format!("{}: {}", prefix, msg)),
+        StdError::SerializeErr {
+            source_type, msg, ..
+        } => StdError::serialize_err(source_type, format!("{}: {}", prefix, msg)),
+        err => err,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::mock::mock_dependencies;
+    use crate::mock::{mock_dependencies, MockApi, MockQuerier, MockStorage};
+    use crate::{
+        coins, from_slice, to_binary, Binary, Coin, ContractResult, QuerierResult, QueryRequest,
+        SystemResult, WasmQuery,
+    };
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
 
     // ensure we can call these many times, eg. as sub-calls
     fn execute(mut deps: DepsMut) {
@@ -85,4 +225,163 @@ mod tests {
         execute(deps.as_mut());
         query(deps.as_ref())
     }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+    struct MyQuery;
+    impl CustomQuery for MyQuery {}
+
+    // a helper that does not care about the custom query type
+    fn balance(deps: Deps, address: &str) -> Coin {
+        deps.querier.query_balance(address, "ATOM").unwrap()
+    }
+
+    #[test]
+    fn into_empty_works() {
+        let storage = MockStorage::new();
+        let api = MockApi::default();
+        let querier: MockQuerier<MyQuery> = MockQuerier::new(&[("alice", &coins(12, "ATOM"))]);
+
+        let deps: Deps<MyQuery> = Deps {
+            storage: &storage,
+            api: &api,
+            querier: QuerierWrapper::new(&querier),
+        };
+        assert_eq!(balance(deps.into_empty(), "alice"), Coin::new(12, "ATOM"));
+
+        let mut storage = MockStorage::new();
+        let deps_mut: DepsMut<MyQuery> = DepsMut {
+            storage: &mut storage,
+            api: &api,
+            querier: QuerierWrapper::new(&querier),
+        };
+        let deps_mut: DepsMut = deps_mut.into_empty();
+        assert_eq!(balance(deps_mut.as_ref(), "alice"), Coin::new(12, "ATOM"));
+    }
+
+    #[test]
+    fn as_ref_scoped_works() {
+        let mut deps = mock_dependencies(&coins(5, "ATOM"));
+        let deps_mut = deps.as_mut();
+        deps_mut.storage.set(b"foo", b"bar");
+
+        let value = deps_mut.as_ref_scoped(|deps| {
+            assert_eq!(
+                balance(deps, crate::mock::MOCK_CONTRACT_ADDR),
+                Coin::new(5, "ATOM")
+            );
+            deps.storage.get(b"foo")
+        });
+        assert_eq!(value, Some(b"bar".to_vec()));
+
+        // writing is possible again after the closure
+        deps_mut.storage.set(b"foo", b"baz");
+        assert_eq!(deps.storage.get(b"foo"), Some(b"baz".to_vec()));
+    }
+
+    #[test]
+    fn into_querier_works() {
+        let storage = MockStorage::new();
+        let api = MockApi::default();
+        let querier: MockQuerier<MyQuery> = MockQuerier::new(&[]).with_custom_handler(|_| {
+            SystemResult::Ok(ContractResult::Ok(to_binary("mine").unwrap()))
+        });
+
+        // the dependencies do not know about the custom query type
+        let deps: Deps = Deps {
+            storage: &storage,
+            api: &api,
+            querier: QuerierWrapper::new(&querier),
+        };
+        let answer: String = deps
+            .into_querier::<MyQuery>()
+            .query(&QueryRequest::Custom(MyQuery))
+            .unwrap();
+        assert_eq!(answer, "mine");
+    }
+
+    #[test]
+    fn with_querier_works() {
+        let mut deps = mock_dependencies(&[]);
+        deps.storage.set(b"foo", b"bar");
+        let stub = MockQuerier::<Empty>::new(&[("alice", &coins(7, "ATOM"))]);
+
+        let mut deps_mut = deps.as_mut();
+        let stubbed = deps_mut.with_querier(&stub);
+        assert_eq!(balance(stubbed.as_ref(), "alice"), Coin::new(7, "ATOM"));
+        // storage is unchanged
+        assert_eq!(stubbed.storage.get(b"foo"), Some(b"bar".to_vec()));
+        // original querier knows nothing about alice
+        assert_eq!(balance(deps_mut.as_ref(), "alice"), Coin::new(0, "ATOM"));
+
+        let deps_ref = deps.as_ref();
+        let stubbed = deps_ref.with_querier(&stub);
+        assert_eq!(balance(stubbed, "alice"), Coin::new(7, "ATOM"));
+        assert_eq!(balance(deps_ref, "alice"), Coin::new(0, "ATOM"));
+    }
+
+    /// Answers all smart queries with a fixed response and forwards everything else
+    struct SmartQueryStub<Q: Querier> {
+        inner: Q,
+        response: Binary,
+    }
+
+    impl<Q: Querier> Querier for SmartQueryStub<Q> {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            match from_slice(bin_request) {
+                Ok(QueryRequest::<Empty>::Wasm(WasmQuery::Smart { .. })) => {
+                    SystemResult::Ok(ContractResult::Ok(self.response.clone()))
+                }
+                _ => self.inner.raw_query(bin_request),
+            }
+        }
+    }
+
+    #[test]
+    fn change_querier_works() {
+        let mut deps = mock_dependencies(&coins(5, "ATOM"));
+        deps.storage.set(b"foo", b"bar");
+
+        let deps = deps.change_querier(|querier| SmartQueryStub {
+            inner: querier,
+            response: to_binary("hello").unwrap(),
+        });
+        assert_eq!(deps.storage.get(b"foo"), Some(b"bar".to_vec()));
+
+        let deps = deps.as_ref();
+        let answer: String = deps
+            .querier
+            .query_wasm_smart("some contract", &Empty {})
+            .unwrap();
+        assert_eq!(answer, "hello");
+        // other queries go to the wrapped querier
+        assert_eq!(
+            balance(deps, crate::mock::MOCK_CONTRACT_ADDR),
+            Coin::new(5, "ATOM")
+        );
+    }
+
+    #[test]
+    fn validate_addresses_works() {
+        let deps = mock_dependencies(&[]);
+        let deps = deps.as_ref();
+
+        let addrs = vec!["alice".to_string(), "bob".to_string()];
+        assert_eq!(
+            deps.validate_addresses(&addrs).unwrap(),
+            vec![Addr::unchecked("alice"), Addr::unchecked("bob")]
+        );
+        assert_eq!(deps.validate_addresses(&[]).unwrap(), Vec::<Addr>::new());
+    }
+
+    #[test]
+    fn validate_addresses_reports_invalid_entry() {
+        let deps = mock_dependencies(&[]);
+        let deps = deps.as_ref();
+
+        let addrs = vec!["alice".to_string(), "x".to_string(), "bob".to_string()];
+        assert_eq!(
+            deps.validate_addresses(&addrs).unwrap_err(),
+            StdError::generic_err("x: Invalid input: human address too short")
+        );
+    }
 }
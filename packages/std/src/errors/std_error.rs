@@ -3,7 +3,7 @@ use std::backtrace::Backtrace;
 use std::fmt;
 use thiserror::Error;
 
-use crate::errors::{RecoverPubkeyError, VerificationError};
+use crate::errors::{RecoverPubkeyError, SystemError, VerificationError};
 
 /// Structured error type for init, execute and query.
 ///
@@ -102,6 +102,20 @@ pub enum StdError {
         #[cfg(feature = "backtraces")]
         backtrace: Backtrace,
     },
+    /// The querier failed to process a query, e.g. because the queried contract does not exist
+    #[error("Querier system error: {source}")]
+    QuerierSystemErr {
+        source: SystemError,
+        #[cfg(feature = "backtraces")]
+        backtrace: Backtrace,
+    },
+    /// The queried contract returned an error
+    #[error("Querier contract error: {msg}")]
+    QuerierContractErr {
+        msg: String,
+        #[cfg(feature = "backtraces")]
+        backtrace: Backtrace,
+    },
 }
 
 impl StdError {
@@ -196,6 +210,22 @@ impl StdError {
             backtrace: Backtrace::capture(),
         }
     }
+
+    pub fn querier_system_err(source: SystemError) -> Self {
+        StdError::QuerierSystemErr {
+            source,
+            #[cfg(feature = "backtraces")]
+            backtrace: Backtrace::capture(),
+        }
+    }
+
+    pub fn querier_contract_err(msg: impl Into<String>) -> Self {
+        StdError::QuerierContractErr {
+            msg: msg.into(),
+            #[cfg(feature = "backtraces")]
+            backtrace: Backtrace::capture(),
+        }
+    }
 }
 
 impl PartialEq<StdError> for StdError {
@@ -399,6 +429,38 @@ impl PartialEq<StdError> for StdError {
                     false
                 }
             }
+            StdError::QuerierSystemErr {
+                source,
+                #[cfg(feature = "backtraces")]
+                    backtrace: _,
+            } => {
+                if let StdError::QuerierSystemErr {
+                    source: rhs_source,
+                    #[cfg(feature = "backtraces")]
+                        backtrace: _,
+                } = rhs
+                {
+                    source == rhs_source
+                } else {
+                    false
+                }
+            }
+            StdError::QuerierContractErr {
+                msg,
+                #[cfg(feature = "backtraces")]
+                    backtrace: _,
+            } => {
+                if let StdError::QuerierContractErr {
+                    msg: rhs_msg,
+                    #[cfg(feature = "backtraces")]
+                        backtrace: _,
+                } = rhs
+                {
+                    msg == rhs_msg
+                } else {
+                    false
+                }
+            }
         }
     }
 }
@@ -708,6 +770,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn querier_system_err_works() {
+        let error = StdError::querier_system_err(SystemError::NoSuchContract {
+            addr: "foo".to_string(),
+        });
+        assert_eq!(
+            error.to_string(),
+            "Querier system error: No such contract: foo"
+        );
+        match error {
+            StdError::QuerierSystemErr {
+                source: SystemError::NoSuchContract { addr },
+                ..
+            } => assert_eq!(addr, "foo"),
+            _ => panic!("expect different error"),
+        }
+    }
+
+    #[test]
+    fn querier_contract_err_works() {
+        let error = StdError::querier_contract_err("not authorized");
+        assert_eq!(error.to_string(), "Querier contract error: not authorized");
+        match error {
+            StdError::QuerierContractErr { msg, .. } => assert_eq!(msg, "not authorized"),
+            _ => panic!("expect different error"),
+        }
+    }
+
     #[test]
     fn implements_debug() {
         let error: StdError = StdError::from(OverflowError::new(OverflowOperation::Sub, 3, 5));
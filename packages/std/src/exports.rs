@@ -29,6 +29,10 @@ extern "C" fn requires_staking() -> () {}
 #[no_mangle]
 extern "C" fn requires_stargate() -> () {}
 
+#[cfg(feature = "bank_supply")]
+#[no_mangle]
+extern "C" fn requires_bank_supply() -> () {}
+
 /// interface_version_* exports mark which Wasm VM interface level this contract is compiled for.
 /// They can be checked by cosmwasm_vm.
 /// Update this whenever the Wasm VM interface breaks.
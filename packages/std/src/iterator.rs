@@ -9,7 +9,7 @@ pub type Pair<V = Vec<u8>> = (Vec<u8>, V);
 #[allow(clippy::upper_case_acronyms)]
 pub type KV<V = Vec<u8>> = Pair<V>;
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 // We assign these to integers to provide a stable API for passing over FFI (to wasm and Go)
 pub enum Order {
     Ascending = 1,
@@ -4,6 +4,8 @@
 
 mod addresses;
 mod binary;
+mod cached_deps;
+mod caching_querier;
 mod coins;
 mod conversion;
 mod deps;
@@ -26,6 +28,8 @@ mod types;
 #[allow(deprecated)]
 pub use crate::addresses::{Addr, CanonicalAddr, HumanAddr};
 pub use crate::binary::Binary;
+pub use crate::cached_deps::CachedDeps;
+pub use crate::caching_querier::CachingQuerier;
 pub use crate::coins::{coin, coins, has_coins, Coin};
 pub use crate::deps::{Deps, DepsMut, OwnedDeps};
 pub use crate::errors::{
@@ -42,8 +46,11 @@ pub use crate::ibc::{
 #[allow(deprecated)]
 pub use crate::iterator::{Order, Pair, KV};
 pub use crate::math::{Decimal, Decimal256, Fraction, Uint128, Uint256, Uint512, Uint64};
+#[cfg(feature = "bank_supply")]
+pub use crate::query::SupplyResponse;
 pub use crate::query::{
-    AllBalanceResponse, BalanceResponse, BankQuery, CustomQuery, QueryRequest, WasmQuery,
+    AllBalanceResponse, BalanceResponse, BankQuery, ContractInfoResponse, CustomQuery,
+    QueryRequest, WasmQuery,
 };
 #[cfg(feature = "staking")]
 pub use crate::query::{
@@ -64,7 +71,7 @@ pub use crate::results::{GovMsg, VoteOption};
 pub use crate::serde::{from_binary, from_slice, to_binary, to_vec};
 pub use crate::storage::MemoryStorage;
 pub use crate::timestamp::Timestamp;
-pub use crate::traits::{Api, Querier, QuerierResult, QuerierWrapper, Storage};
+pub use crate::traits::{Api, DebugLevel, Querier, QuerierResult, QuerierWrapper, Storage};
 pub use crate::types::{BlockInfo, ContractInfo, Env, MessageInfo};
 
 // Exposed in wasm build only
@@ -99,9 +106,10 @@ pub mod testing {
     #[cfg(feature = "staking")]
     pub use crate::mock::StakingQuerier;
     pub use crate::mock::{
-        digit_sum, mock_dependencies, mock_dependencies_with_balances, mock_env, mock_info,
-        mock_wasmd_attr, riffle_shuffle, BankQuerier, MockApi, MockQuerier,
-        MockQuerierCustomHandlerResult, MockStorage, MOCK_CONTRACT_ADDR,
+        digit_sum, mock_dependencies, mock_dependencies_with_balances,
+        mock_dependencies_with_storage, mock_env, mock_info, mock_wasmd_attr, riffle_shuffle,
+        BankQuerier, MockApi, MockQuerier, MockQuerierCustomHandlerResult, MockStorage,
+        RecordingStorage, StorageOp, MOCK_CONTRACT_ADDR,
     };
     #[cfg(feature = "stargate")]
     pub use crate::mock::{
@@ -32,10 +32,10 @@ impl Decimal256 {
         ]);
 
     pub const MAX: Self = Self(Uint256::MAX);
-    
-    /// Creates a Decimal(value).
+
+    /// Creates a Decimal256(value).
     pub const fn new(value: Uint256) -> Self {
-        Decimal(value)
+        Self(value)
     }
 
     /// Create a 1.0 Decimal256
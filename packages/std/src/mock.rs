@@ -1,6 +1,7 @@
 use serde::de::DeserializeOwned;
 #[cfg(feature = "stargate")]
 use serde::Serialize;
+use std::cell::{Ref, RefCell};
 use std::collections::HashMap;
 
 use crate::addresses::{Addr, CanonicalAddr};
@@ -14,6 +15,10 @@ use crate::ibc::{
     IbcEndpoint, IbcOrder, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg,
     IbcTimeoutBlock,
 };
+#[cfg(feature = "iterator")]
+use crate::iterator::{Order, Pair};
+#[cfg(feature = "bank_supply")]
+use crate::query::SupplyResponse;
 use crate::query::{
     AllBalanceResponse, BalanceResponse, BankQuery, CustomQuery, QueryRequest, WasmQuery,
 };
@@ -26,7 +31,7 @@ use crate::results::{ContractResult, Empty, SystemResult};
 use crate::serde::{from_slice, to_binary};
 use crate::storage::MemoryStorage;
 use crate::timestamp::Timestamp;
-use crate::traits::{Api, Querier, QuerierResult};
+use crate::traits::{Api, DebugLevel, Querier, QuerierResult, Storage};
 use crate::types::{BlockInfo, ContractInfo, Env, MessageInfo};
 use crate::Attribute;
 
@@ -56,10 +61,123 @@ pub fn mock_dependencies_with_balances(
     }
 }
 
+/// Initializes the storage with the given key/value pairs, inserted in the given order.
+/// Later entries overwrite earlier entries with the same key. No balances are set.
+pub fn mock_dependencies_with_storage(
+    initial: &[(&[u8], &[u8])],
+) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut storage = MockStorage::default();
+    for (key, value) in initial {
+        storage.set(key, value);
+    }
+    OwnedDeps {
+        storage,
+        api: MockApi::default(),
+        querier: MockQuerier::new(&[]),
+    }
+}
+
 // Use MemoryStorage implementation (which is valid in non-testcode)
 // We can later make simplifications here if needed
 pub type MockStorage = MemoryStorage;
 
+impl MockStorage {
+    /// Creates an empty storage that records all operations performed on it.
+    /// The log can be inspected via `RecordingStorage::access_log`.
+    pub fn with_recording() -> RecordingStorage<MockStorage> {
+        RecordingStorage::new(MockStorage::new())
+    }
+}
+
+/// A storage operation recorded by a `RecordingStorage`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StorageOp {
+    Get {
+        key: Vec<u8>,
+    },
+    Set {
+        key: Vec<u8>,
+        value: Vec<u8>,
+    },
+    Remove {
+        key: Vec<u8>,
+    },
+    #[cfg(feature = "iterator")]
+    Range {
+        start: Option<Vec<u8>>,
+        end: Option<Vec<u8>>,
+        order: Order,
+    },
+}
+
+/// A storage wrapper that records all operations performed on the inner storage
+/// before forwarding them.
+pub struct RecordingStorage<S: Storage> {
+    inner: S,
+    access_log: RefCell<Vec<StorageOp>>,
+}
+
+impl<S: Storage> RecordingStorage<S> {
+    pub fn new(inner: S) -> Self {
+        RecordingStorage {
+            inner,
+            access_log: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns all operations performed on this storage in chronological order.
+    ///
+    /// The log is borrowed from a `RefCell`, so the returned guard must be dropped
+    /// before the storage is used again.
+    pub fn access_log(&self) -> Ref<'_, [StorageOp]> {
+        Ref::map(self.access_log.borrow(), |log| log.as_slice())
+    }
+
+    /// Consumes the wrapper and returns the inner storage
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn record(&self, op: StorageOp) {
+        self.access_log.borrow_mut().push(op);
+    }
+}
+
+impl<S: Storage> Storage for RecordingStorage<S> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.record(StorageOp::Get { key: key.to_vec() });
+        self.inner.get(key)
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.record(StorageOp::Set {
+            key: key.to_vec(),
+            value: value.to_vec(),
+        });
+        self.inner.set(key, value);
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.record(StorageOp::Remove { key: key.to_vec() });
+        self.inner.remove(key);
+    }
+
+    #[cfg(feature = "iterator")]
+    fn range<'a>(
+        &'a self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Pair> + 'a> {
+        self.record(StorageOp::Range {
+            start: start.map(|s| s.to_vec()),
+            end: end.map(|e| e.to_vec()),
+            order,
+        });
+        self.inner.range(start, end, order)
+    }
+}
+
 /// Length of canonical addresses created with this API. Contracts should not make any assumtions
 /// what this value is.
 /// The value here must be restorable with `SHUFFLES_ENCODE` + `SHUFFLES_DECODE` in-shuffles.
@@ -71,28 +189,74 @@ const SHUFFLES_DECODE: usize = 2;
 // MockPrecompiles zero pads all human addresses to make them fit the canonical_length
 // it trims off zeros for the reverse operation.
 // not really smart, but allows us to see a difference (and consistent length for canonical adddresses)
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct MockApi {
     /// Length of canonical addresses created with this API. Contracts should not make any assumtions
     /// what this value is.
     canonical_length: usize,
+    /// Number of shuffles restoring the input after `SHUFFLES_ENCODE` shuffles
+    shuffles_decode: usize,
+    /// Prefix of all human addresses, which is not part of the canonical address
+    bech32_prefix: String,
+    /// All debugging messages emitted so far
+    debug_messages: RefCell<Vec<(DebugLevel, String)>>,
 }
 
 impl Default for MockApi {
     fn default() -> Self {
         MockApi {
             canonical_length: CANONICAL_LENGTH,
+            shuffles_decode: SHUFFLES_DECODE,
+            bech32_prefix: String::new(),
+            debug_messages: RefCell::new(Vec::new()),
         }
     }
 }
 
-impl Api for MockApi {
-    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
-        self.addr_canonicalize(human).map(|_canonical| ())?;
-        Ok(Addr::unchecked(human))
+impl MockApi {
+    /// Creates a MockApi simulating a chain with different address formats.
+    ///
+    /// Human addresses must start with `bech32_prefix`. The rest of the human address is stored
+    /// in canonical addresses of `canonical_length` bytes, which must be an even number of at
+    /// least 4. Shorter canonical addresses could not store any human address, since those are
+    /// at least 3 bytes long.
+    ///
+    /// Panics if `canonical_length` is odd or less than 4.
+    pub fn new(canonical_length: usize, bech32_prefix: &str) -> Self {
+        assert!(
+            canonical_length >= 4,
+            "Canonical length must be at least 4, got {}",
+            canonical_length
+        );
+        // panics for odd lengths
+        let cycle = riffle_shuffle_cycle(canonical_length);
+        MockApi {
+            canonical_length,
+            shuffles_decode: (cycle - SHUFFLES_ENCODE % cycle) % cycle,
+            bech32_prefix: bech32_prefix.to_string(),
+            debug_messages: RefCell::new(Vec::new()),
+        }
     }
 
+    /// Returns all debugging messages emitted via `debug` and `debug_at` with their levels,
+    /// in the order they were emitted. Messages emitted via `debug` have level `Info`.
+    ///
+    /// The messages are borrowed from a `RefCell`, so the returned guard must be dropped
+    /// before emitting further messages.
+    pub fn debug_messages(&self) -> Ref<'_, [(DebugLevel, String)]> {
+        Ref::map(self.debug_messages.borrow(), |messages| messages.as_slice())
+    }
+}
+
+impl Api for MockApi {
     fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        let human = human.strip_prefix(&self.bech32_prefix).ok_or_else(|| {
+            StdError::generic_err(format!(
+                "Invalid input: human address must start with {}",
+                self.bech32_prefix
+            ))
+        })?;
+
         // Dummy input validation. This is more sophisticated for formats like bech32, where format and checksum are validated.
         if human.len() < 3 {
             return Err(StdError::generic_err(
@@ -127,8 +291,8 @@ impl Api for MockApi {
         }
 
         let mut tmp: Vec<u8> = canonical.clone().into();
-        // Shuffle two more times which restored the original value (24 elements are back to original after 20 rounds)
-        for _ in 0..SHUFFLES_DECODE {
+        // Shuffle some more times which restores the original value (e.g. 54 elements are back to original after 20 rounds)
+        for _ in 0..self.shuffles_decode {
             tmp = riffle_shuffle(&tmp);
         }
         // Rotate back
@@ -138,7 +302,7 @@ impl Api for MockApi {
         let trimmed = tmp.into_iter().filter(|&x| x != 0x00).collect();
         // decode UTF-8 bytes into string
         let human = String::from_utf8(trimmed)?;
-        Ok(Addr::unchecked(human))
+        Ok(Addr::unchecked(format!("{}{}", self.bech32_prefix, human)))
     }
 
     fn secp256k1_verify(
@@ -190,7 +354,17 @@ impl Api for MockApi {
     }
 
     fn debug(&self, message: &str) {
-        println!("{}", message);
+        self.debug_at(DebugLevel::Info, message);
+    }
+
+    fn debug_at(&self, level: DebugLevel, message: &str) {
+        match level {
+            DebugLevel::Info => println!("{}", message),
+            _ => println!("{}: {}", level, message),
+        }
+        self.debug_messages
+            .borrow_mut()
+            .push((level, message.to_string()));
     }
 }
 
@@ -380,15 +554,15 @@ pub fn mock_ibc_packet_timeout(
 pub type MockQuerierCustomHandlerResult = SystemResult<ContractResult<Binary>>;
 
 /// MockQuerier holds an immutable table of bank balances
-/// TODO: also allow querying contracts
+/// and handlers for wasm and custom queries
 pub struct MockQuerier<C: DeserializeOwned = Empty> {
     bank: BankQuerier,
     #[cfg(feature = "staking")]
     staking: StakingQuerier,
-    // placeholder to add support later
-    wasm: NoWasmQuerier,
+    /// Answers with `NoSuchContract` for all contracts by default. Update it via `update_wasm`.
+    wasm: WasmQuerier,
     /// A handler to handle custom queries. This is set to a dummy handler that
-    /// always errors by default. Update it via `with_custom_handler`.
+    /// always errors by default. Update it via `with_custom_handler` or `update_custom`.
     ///
     /// Use box to avoid the need of another generic type
     custom_handler: Box<dyn for<'a> Fn(&'a C) -> MockQuerierCustomHandlerResult>,
@@ -400,7 +574,7 @@ impl<C: DeserializeOwned> MockQuerier<C> {
             bank: BankQuerier::new(balances),
             #[cfg(feature = "staking")]
             staking: StakingQuerier::default(),
-            wasm: NoWasmQuerier {},
+            wasm: WasmQuerier::default(),
             // strange argument notation suggested as a workaround here: https://github.com/rust-lang/rust/issues/41078#issuecomment-294296365
             custom_handler: Box::from(|_: &_| -> MockQuerierCustomHandlerResult {
                 SystemResult::Err(SystemError::UnsupportedRequest {
@@ -429,13 +603,31 @@ impl<C: DeserializeOwned> MockQuerier<C> {
         self.staking = StakingQuerier::new(denom, validators, delegations);
     }
 
+    /// Sets the handler for all wasm queries, replacing the default which
+    /// answers with `NoSuchContract`
+    pub fn update_wasm<WH>(&mut self, handler: WH)
+    where
+        WH: Fn(&WasmQuery) -> QuerierResult + 'static,
+    {
+        self.wasm = WasmQuerier::new(Box::new(handler));
+    }
+
     pub fn with_custom_handler<CH: 'static>(mut self, handler: CH) -> Self
     where
         CH: Fn(&C) -> MockQuerierCustomHandlerResult,
     {
-        self.custom_handler = Box::from(handler);
+        self.update_custom(handler);
         self
     }
+
+    /// Sets the handler for custom queries. This is the same as `with_custom_handler`
+    /// for a querier that is already in use.
+    pub fn update_custom<CH>(&mut self, handler: CH)
+    where
+        CH: Fn(&C) -> MockQuerierCustomHandlerResult + 'static,
+    {
+        self.custom_handler = Box::from(handler);
+    }
 }
 
 impl<C: CustomQuery + DeserializeOwned> Querier for MockQuerier<C> {
@@ -473,19 +665,36 @@ impl<C: CustomQuery + DeserializeOwned> MockQuerier<C> {
     }
 }
 
-#[derive(Clone, Default)]
-struct NoWasmQuerier {
-    // FIXME: actually provide a way to call out
+struct WasmQuerier {
+    /// A handler to handle Wasm queries. This is set to a dummy handler that
+    /// always returns `NoSuchContract` by default.
+    ///
+    /// Use box to avoid the need of generic type.
+    handler: Box<dyn for<'a> Fn(&'a WasmQuery) -> QuerierResult>,
 }
 
-impl NoWasmQuerier {
+impl WasmQuerier {
+    fn new(handler: Box<dyn for<'a> Fn(&'a WasmQuery) -> QuerierResult>) -> Self {
+        Self { handler }
+    }
+
     fn query(&self, request: &WasmQuery) -> QuerierResult {
-        let addr = match request {
-            WasmQuery::Smart { contract_addr, .. } => contract_addr,
-            WasmQuery::Raw { contract_addr, .. } => contract_addr,
-        }
-        .clone();
-        SystemResult::Err(SystemError::NoSuchContract { addr })
+        (*self.handler)(request)
+    }
+}
+
+impl Default for WasmQuerier {
+    fn default() -> Self {
+        let handler = Box::from(|request: &WasmQuery| -> QuerierResult {
+            let addr = match request {
+                WasmQuery::Smart { contract_addr, .. } => contract_addr,
+                WasmQuery::Raw { contract_addr, .. } => contract_addr,
+                WasmQuery::ContractInfo { contract_addr, .. } => contract_addr,
+            }
+            .clone();
+            SystemResult::Err(SystemError::NoSuchContract { addr })
+        });
+        Self::new(handler)
     }
 }
 
@@ -527,6 +736,24 @@ impl BankQuerier {
                 };
                 to_binary(&bank_res).into()
             }
+            #[cfg(feature = "bank_supply")]
+            BankQuery::Supply { denom } => {
+                // the supply is the sum of all balances in this denom
+                let amount = self
+                    .balances
+                    .values()
+                    .flatten()
+                    .filter(|c| &c.denom == denom)
+                    .map(|c| c.amount)
+                    .sum();
+                let bank_res = SupplyResponse {
+                    amount: Coin {
+                        amount,
+                        denom: denom.to_string(),
+                    },
+                };
+                to_binary(&bank_res).into()
+            }
         };
         // system result is always ok in the mock implementation
         SystemResult::Ok(contract_result)
@@ -666,6 +893,18 @@ pub fn riffle_shuffle<T: Clone>(input: &[T]) -> Vec<T> {
     out
 }
 
+/// Returns the number of riffle shuffles after which a list of `len` elements is back in its original order
+fn riffle_shuffle_cycle(len: usize) -> usize {
+    let original: Vec<usize> = (0..len).collect();
+    let mut shuffled = riffle_shuffle(&original);
+    let mut rounds = 1;
+    while shuffled != original {
+        shuffled = riffle_shuffle(&shuffled);
+        rounds += 1;
+    }
+    rounds
+}
+
 pub fn digit_sum(input: &[u8]) -> usize {
     input.iter().fold(0, |sum, val| sum + (*val as usize))
 }
@@ -682,7 +921,7 @@ pub fn mock_wasmd_attr(key: impl Into<String>, value: impl Into<String>) -> Attr
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{coin, coins, from_binary};
+    use crate::{coin, coins, from_binary, QuerierWrapper};
     #[cfg(feature = "staking")]
     use crate::{Decimal, Delegation};
     use hex_literal::hex;
@@ -712,6 +951,92 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mock_dependencies_with_storage_works() {
+        let deps =
+            mock_dependencies_with_storage(&[(b"foo", b"bar"), (b"key", b"one"), (b"key", b"two")]);
+        assert_eq!(deps.storage.get(b"foo"), Some(b"bar".to_vec()));
+        // last write wins
+        assert_eq!(deps.storage.get(b"key"), Some(b"two".to_vec()));
+        assert_eq!(deps.storage.get(b"other"), None);
+    }
+
+    #[test]
+    fn with_recording_logs_operations() {
+        let mut store = MockStorage::with_recording();
+        store.set(b"foo", b"bar");
+        store.get(b"foo");
+        store.get(b"missing");
+        store.remove(b"foo");
+
+        assert_eq!(
+            *store.access_log(),
+            [
+                StorageOp::Set {
+                    key: b"foo".to_vec(),
+                    value: b"bar".to_vec(),
+                },
+                StorageOp::Get {
+                    key: b"foo".to_vec(),
+                },
+                StorageOp::Get {
+                    key: b"missing".to_vec(),
+                },
+                StorageOp::Remove {
+                    key: b"foo".to_vec(),
+                },
+            ]
+        );
+        assert_eq!(store.into_inner().get(b"foo"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn with_recording_logs_range() {
+        let mut store = MockStorage::with_recording();
+        store.set(b"b", b"1");
+        let items: Vec<Pair> = store.range(Some(b"a"), None, Order::Descending).collect();
+        assert_eq!(items, vec![(b"b".to_vec(), b"1".to_vec())]);
+
+        assert_eq!(
+            store.access_log()[1],
+            StorageOp::Range {
+                start: Some(b"a".to_vec()),
+                end: None,
+                order: Order::Descending,
+            }
+        );
+    }
+
+    #[test]
+    fn mock_dependencies_with_balances_works() {
+        let deps = mock_dependencies_with_balances(&[
+            ("alice", &coins(100, "atom")),
+            ("bob", &[coin(5, "atom"), coin(12, "btc")]),
+        ]);
+        let querier = deps.as_ref().querier;
+
+        assert_eq!(
+            querier.query_all_balances("alice").unwrap(),
+            coins(100, "atom")
+        );
+        assert_eq!(
+            querier.query_all_balances("bob").unwrap(),
+            vec![coin(5, "atom"), coin(12, "btc")]
+        );
+        assert_eq!(
+            querier.query_balance("bob", "btc").unwrap(),
+            coin(12, "btc")
+        );
+
+        // unseeded addresses have no balance
+        assert_eq!(querier.query_all_balances("carl").unwrap(), vec![]);
+        assert_eq!(
+            querier.query_balance("carl", "atom").unwrap(),
+            coin(0, "atom")
+        );
+    }
+
     #[test]
     fn canonicalize_and_humanize_restores_original() {
         let api = MockApi::default();
@@ -722,6 +1047,99 @@ mod tests {
         assert_eq!(recovered, original);
     }
 
+    #[test]
+    fn debug_at_collects_messages() {
+        let api = MockApi::default();
+        api.debug("starting");
+        api.debug_at(DebugLevel::Warn, "low balance");
+        api.debug_at(DebugLevel::Error, "out of funds");
+        api.debug_at(DebugLevel::Warn, "retrying");
+
+        assert_eq!(
+            *api.debug_messages(),
+            [
+                (DebugLevel::Info, "starting".to_string()),
+                (DebugLevel::Warn, "low balance".to_string()),
+                (DebugLevel::Error, "out of funds".to_string()),
+                (DebugLevel::Warn, "retrying".to_string()),
+            ]
+        );
+
+        let warnings: Vec<String> = api
+            .debug_messages()
+            .iter()
+            .filter(|(level, _)| *level == DebugLevel::Warn)
+            .map(|(_, message)| message.clone())
+            .collect();
+        assert_eq!(warnings, vec!["low balance", "retrying"]);
+    }
+
+    #[test]
+    fn addr_validate_works() {
+        let api = MockApi::default();
+
+        let addr = api.addr_validate("foobar123").unwrap();
+        assert_eq!(addr, Addr::unchecked("foobar123"));
+
+        // too short
+        let err = api.addr_validate("1").unwrap_err();
+        assert!(err.to_string().contains("address too short"));
+
+        // does not round-trip, since the padding is stripped when humanizing
+        let err = api.addr_validate("foo\0bar").unwrap_err();
+        assert!(err.to_string().contains("round-trip"));
+    }
+
+    #[test]
+    fn custom_prefix_and_length_round_trip() {
+        let api = MockApi::new(32, "juno1");
+
+        let original = "juno1shorty";
+        let canonical = api.addr_canonicalize(original).unwrap();
+        assert_eq!(canonical.len(), 32);
+        let recovered = api.addr_humanize(&canonical).unwrap();
+        assert_eq!(recovered, original);
+        assert_eq!(api.addr_validate(original).unwrap(), original);
+
+        // missing prefix
+        let err = api.addr_canonicalize("cosmos1shorty").unwrap_err();
+        assert!(err.to_string().contains("must start with juno1"));
+
+        // too long for 32 bytes (the prefix does not count)
+        let too_long = format!("juno1{}", "a".repeat(33));
+        let err = api.addr_canonicalize(&too_long).unwrap_err();
+        assert!(err.to_string().contains("address too long"));
+        let max = format!("juno1{}", "a".repeat(32));
+        api.addr_canonicalize(&max).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "even number of elements")]
+    fn new_panics_for_odd_length() {
+        MockApi::new(33, "");
+    }
+
+    #[test]
+    #[should_panic(expected = "Canonical length must be at least 4, got 0")]
+    fn new_panics_for_zero_length() {
+        MockApi::new(0, "");
+    }
+
+    #[test]
+    #[should_panic(expected = "Canonical length must be at least 4, got 2")]
+    fn new_panics_for_too_short_length() {
+        MockApi::new(2, "");
+    }
+
+    #[test]
+    fn custom_length_round_trips_for_many_lengths() {
+        for length in (4..=100).step_by(2) {
+            let api = MockApi::new(length, "");
+            let canonical = api.addr_canonicalize("foo").unwrap();
+            assert_eq!(api.addr_humanize(&canonical).unwrap(), "foo");
+        }
+    }
+
     #[test]
     #[should_panic(expected = "address too short")]
     fn addr_canonicalize_min_input_length() {
@@ -963,6 +1381,55 @@ mod tests {
         assert_eq!(res.unwrap_err(), VerificationError::InvalidPubkeyFormat);
     }
 
+    #[test]
+    fn wasm_querier_works() {
+        let mut querier: MockQuerier<Empty> = MockQuerier::new(&[("alice", &coins(5, "atom"))]);
+
+        // defaults to no contracts
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+        let err = wrapper
+            .query_wasm_smart::<String>("counter", &Empty {})
+            .unwrap_err();
+        assert!(err.to_string().contains("No such contract: counter"));
+
+        querier.update_wasm(|request| match request {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "counter" => {
+                SystemResult::Ok(ContractResult::Ok(to_binary("canned").unwrap()))
+            }
+            WasmQuery::Smart { contract_addr, .. }
+            | WasmQuery::Raw { contract_addr, .. }
+            | WasmQuery::ContractInfo { contract_addr } => {
+                SystemResult::Err(SystemError::NoSuchContract {
+                    addr: contract_addr.clone(),
+                })
+            }
+        });
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+        let response: String = wrapper.query_wasm_smart("counter", &Empty {}).unwrap();
+        assert_eq!(response, "canned");
+        let err = wrapper
+            .query_wasm_smart::<String>("other", &Empty {})
+            .unwrap_err();
+        assert!(err.to_string().contains("No such contract: other"));
+
+        // bank queries still work
+        assert_eq!(
+            wrapper.query_balance("alice", "atom").unwrap(),
+            coin(5, "atom")
+        );
+    }
+
+    #[test]
+    fn update_custom_works() {
+        let mut querier: MockQuerier<Empty> = MockQuerier::new(&[]);
+        querier
+            .update_custom(|_| SystemResult::Ok(ContractResult::Ok(to_binary("custom").unwrap())));
+
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+        let response: String = wrapper.query(&QueryRequest::Custom(Empty {})).unwrap();
+        assert_eq!(response, "custom");
+    }
+
     #[test]
     fn bank_querier_all_balances() {
         let addr = String::from("foobar");
@@ -1034,6 +1501,34 @@ mod tests {
         assert_eq!(res.amount, coin(0, "ELF"));
     }
 
+    #[cfg(feature = "bank_supply")]
+    #[test]
+    fn bank_querier_supply() {
+        let bank = BankQuerier::new(&[
+            ("alice", &[coin(123, "ELF"), coin(777, "FLY")]),
+            ("bob", &[coin(100, "ELF")]),
+        ]);
+
+        let res = bank
+            .query(&BankQuery::Supply {
+                denom: "ELF".to_string(),
+            })
+            .unwrap()
+            .unwrap();
+        let res: SupplyResponse = from_binary(&res).unwrap();
+        assert_eq!(res.amount, coin(223, "ELF"));
+
+        // unknown denom has zero supply
+        let res = bank
+            .query(&BankQuery::Supply {
+                denom: "BTC".to_string(),
+            })
+            .unwrap()
+            .unwrap();
+        let res: SupplyResponse = from_binary(&res).unwrap();
+        assert_eq!(res.amount, coin(0, "BTC"));
+    }
+
     #[cfg(feature = "staking")]
     #[test]
     fn staking_querier_all_validators() {
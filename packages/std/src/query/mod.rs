@@ -11,6 +11,8 @@ mod staking;
 mod stargate;
 mod wasm;
 
+#[cfg(feature = "bank_supply")]
+pub use bank::SupplyResponse;
 pub use bank::{AllBalanceResponse, BalanceResponse, BankQuery};
 #[cfg(feature = "stargate")]
 pub use ibc::{ChannelResponse, IbcQuery, ListChannelsResponse, PortIdResponse};
@@ -21,7 +23,7 @@ pub use staking::{
 };
 #[cfg(feature = "stargate")]
 pub use stargate::StargateResponse;
-pub use wasm::WasmQuery;
+pub use wasm::{ContractInfoResponse, WasmQuery};
 
 #[non_exhaustive]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
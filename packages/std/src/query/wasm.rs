@@ -21,4 +21,32 @@ pub enum WasmQuery {
         /// Key is the raw key used in the contracts Storage
         key: Binary,
     },
+    /// returns a ContractInfoResponse with metadata on the contract from the runtime
+    ContractInfo { contract_addr: String },
+}
+
+#[non_exhaustive]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ContractInfoResponse {
+    pub code_id: u64,
+    /// address that instantiated this contract
+    pub creator: String,
+    /// admin who can run migrations (if any)
+    pub admin: Option<String>,
+    /// if set, the contract is pinned to the cache, and thus uses less gas when called
+    pub pinned: bool,
+}
+
+impl ContractInfoResponse {
+    /// Convenience constructor for tests / mocks
+    #[doc(hidden)]
+    pub fn new(code_id: u64, creator: impl Into<String>) -> Self {
+        Self {
+            code_id,
+            creator: creator.into(),
+            admin: None,
+            pinned: false,
+        }
+    }
 }
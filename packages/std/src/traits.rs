@@ -1,4 +1,6 @@
 use serde::{de::DeserializeOwned, Serialize};
+use std::fmt;
+use std::marker::PhantomData;
 use std::ops::Deref;
 
 use crate::addresses::{Addr, CanonicalAddr};
@@ -7,8 +9,11 @@ use crate::coins::Coin;
 use crate::errors::{RecoverPubkeyError, StdError, StdResult, VerificationError};
 #[cfg(feature = "iterator")]
 use crate::iterator::{Order, Pair};
+#[cfg(feature = "bank_supply")]
+use crate::query::SupplyResponse;
 use crate::query::{
-    AllBalanceResponse, BalanceResponse, BankQuery, CustomQuery, QueryRequest, WasmQuery,
+    AllBalanceResponse, BalanceResponse, BankQuery, ContractInfoResponse, CustomQuery,
+    QueryRequest, WasmQuery,
 };
 #[cfg(feature = "staking")]
 use crate::query::{
@@ -77,7 +82,19 @@ pub trait Api {
     /// let validated: Addr = api.addr_validate(input).unwrap();
     /// assert_eq!(validated, input);
     /// ```
-    fn addr_validate(&self, human: &str) -> StdResult<Addr>;
+    ///
+    /// The default implementation canonicalizes and humanizes the input and
+    /// fails if the result is not the original input.
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        let canonical = self.addr_canonicalize(human)?;
+        let normalized = self.addr_humanize(&canonical)?;
+        if normalized != human {
+            return Err(StdError::generic_err(
+                "Invalid input: address does not round-trip through canonicalization",
+            ));
+        }
+        Ok(normalized)
+    }
 
     /// Takes a human readable address and returns a canonical binary representation of it.
     /// This can be used when a compact fixed length representation is needed.
@@ -120,6 +137,36 @@ pub trait Api {
     /// Emits a debugging message that is handled depending on the environment (typically printed to console or ignored).
     /// Those messages are not persisted to chain.
     fn debug(&self, message: &str);
+
+    /// Emits a debugging message with the given level.
+    ///
+    /// The default implementation passes `Info` messages unchanged to `debug`, such that
+    /// `debug_at(DebugLevel::Info, message)` is equivalent to `debug(message)`. Messages of
+    /// other levels are prefixed with the level.
+    fn debug_at(&self, level: DebugLevel, message: &str) {
+        match level {
+            DebugLevel::Info => self.debug(message),
+            _ => self.debug(&format!("{}: {}", level, message)),
+        }
+    }
+}
+
+/// The level of a debugging message emitted via [`Api::debug_at`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DebugLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl fmt::Display for DebugLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DebugLevel::Info => write!(f, "info"),
+            DebugLevel::Warn => write!(f, "warn"),
+            DebugLevel::Error => write!(f, "error"),
+        }
+    }
 }
 
 /// A short-hand alias for the two-level query result (1. accessing the contract, 2. executing query in the contract)
@@ -134,27 +181,57 @@ pub trait Querier {
     fn raw_query(&self, bin_request: &[u8]) -> QuerierResult;
 }
 
-#[derive(Copy, Clone)]
-pub struct QuerierWrapper<'a>(&'a dyn Querier);
+/// QuerierWrapper wraps a querier and provides typed helpers on top of it.
+///
+/// The custom query type `C` is the type used in `QueryRequest::Custom` by [`QuerierWrapper::query`].
+/// It defaults to `Empty` for chains without custom queries.
+pub struct QuerierWrapper<'a, C: CustomQuery = Empty> {
+    querier: &'a dyn Querier,
+    custom_query_type: PhantomData<C>,
+}
+
+// Use custom implementations of Copy and Clone, as `derive` would require `C` to implement them.
+impl<'a, C: CustomQuery> Copy for QuerierWrapper<'a, C> {}
+
+impl<'a, C: CustomQuery> Clone for QuerierWrapper<'a, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
 
 /// This allows us to use self.raw_query to access the querier.
 /// It also allows external callers to access the querier easily.
-impl<'a> Deref for QuerierWrapper<'a> {
+impl<'a, C: CustomQuery> Deref for QuerierWrapper<'a, C> {
     type Target = dyn Querier + 'a;
 
     fn deref(&self) -> &Self::Target {
-        self.0
+        self.querier
     }
 }
 
-impl<'a> QuerierWrapper<'a> {
+impl<'a, C: CustomQuery> QuerierWrapper<'a, C> {
     pub fn new(querier: &'a dyn Querier) -> Self {
-        QuerierWrapper(querier)
+        QuerierWrapper {
+            querier,
+            custom_query_type: PhantomData,
+        }
+    }
+
+    /// Converts this wrapper into one for a different custom query type,
+    /// wrapping the same querier.
+    pub fn into_custom<C2: CustomQuery>(self) -> QuerierWrapper<'a, C2> {
+        QuerierWrapper::new(self.querier)
     }
 
-    /// query is a shorthand for custom_query when we are not using a custom type,
-    /// this allows us to avoid specifying "Empty" in all the type definitions.
-    pub fn query<T: DeserializeOwned>(&self, request: &QueryRequest<Empty>) -> StdResult<T> {
+    /// Converts this wrapper into one that does not support custom queries
+    pub fn into_empty(self) -> QuerierWrapper<'a, Empty> {
+        self.into_custom()
+    }
+
+    /// Makes the query and parses the response, where custom queries are of type `C`.
+    /// This is a shorthand for `custom_query` which avoids specifying the custom query type
+    /// in all the type definitions.
+    pub fn query<T: DeserializeOwned>(&self, request: &QueryRequest<C>) -> StdResult<T> {
         self.custom_query(request)
     }
 
@@ -162,26 +239,41 @@ impl<'a> QuerierWrapper<'a> {
     /// so you need to specify the custom query type in the function parameters.
     /// If you are no using a custom query, just use `query` for easier interface.
     ///
-    /// Any error (System Error, Error or called contract, or Parse Error) are flattened into
-    /// one level. Only use this if you don't need to check the SystemError
-    /// eg. If you don't differentiate between contract missing and contract returned error
-    pub fn custom_query<C: CustomQuery, U: DeserializeOwned>(
+    /// A system error (e.g. the queried contract does not exist) is returned as
+    /// `StdError::QuerierSystemErr` and an error returned by the queried contract as
+    /// `StdError::QuerierContractErr`. A response that cannot be parsed into `U` results in
+    /// `StdError::ParseErr` containing the target type.
+    pub fn custom_query<Q: CustomQuery, U: DeserializeOwned>(
         &self,
-        request: &QueryRequest<C>,
+        request: &QueryRequest<Q>,
     ) -> StdResult<U> {
         let raw = to_vec(request).map_err(|serialize_err| {
             StdError::generic_err(format!("Serializing QueryRequest: {}", serialize_err))
         })?;
-        match self.raw_query(&raw) {
-            SystemResult::Err(system_err) => Err(StdError::generic_err(format!(
-                "Querier system error: {}",
-                system_err
-            ))),
-            SystemResult::Ok(ContractResult::Err(contract_err)) => Err(StdError::generic_err(
-                format!("Querier contract error: {}", contract_err),
-            )),
-            SystemResult::Ok(ContractResult::Ok(value)) => from_binary(&value),
-        }
+        let value = map_query_result(self.raw_query(&raw))?;
+        from_binary(&value)
+    }
+
+    /// Makes all queries in order and returns one result per request, such that a failing
+    /// query does not abort the others. Responses are returned unparsed, use `from_binary`
+    /// to parse them. Errors are mapped like in `custom_query`.
+    ///
+    /// All requests are serialized before the first query is made. The outer error is only
+    /// returned if one of them cannot be serialized.
+    pub fn query_batch(&self, requests: &[QueryRequest<C>]) -> StdResult<Vec<StdResult<Binary>>> {
+        let raws = requests
+            .iter()
+            .map(|request| {
+                to_vec(request).map_err(|serialize_err| {
+                    StdError::generic_err(format!("Serializing QueryRequest: {}", serialize_err))
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        let results = raws
+            .iter()
+            .map(|raw| map_query_result(self.raw_query(raw)))
+            .collect();
+        Ok(results)
     }
 
     pub fn query_balance(
@@ -198,6 +290,23 @@ impl<'a> QuerierWrapper<'a> {
         Ok(res.amount)
     }
 
+    /// Like `query_balance`, but returns an error instead of a zero amount
+    pub fn query_balance_required(
+        &self,
+        address: impl Into<String>,
+        denom: impl Into<String>,
+    ) -> StdResult<Coin> {
+        let address = address.into();
+        let balance = self.query_balance(address.as_str(), denom)?;
+        if balance.amount.is_zero() {
+            return Err(StdError::generic_err(format!(
+                "No {} balance for address {}",
+                balance.denom, address
+            )));
+        }
+        Ok(balance)
+    }
+
     pub fn query_all_balances(&self, address: impl Into<String>) -> StdResult<Vec<Coin>> {
         let request = BankQuery::AllBalances {
             address: address.into(),
@@ -207,6 +316,18 @@ impl<'a> QuerierWrapper<'a> {
         Ok(res.amount)
     }
 
+    /// Queries the total supply of the given denom. Returns a zero amount for unknown denoms.
+    /// Requires a chain that supports the bank supply query, see the `bank_supply` feature.
+    #[cfg(feature = "bank_supply")]
+    pub fn query_supply(&self, denom: impl Into<String>) -> StdResult<Coin> {
+        let request = BankQuery::Supply {
+            denom: denom.into(),
+        }
+        .into();
+        let res: SupplyResponse = self.query(&request)?;
+        Ok(res.amount)
+    }
+
     // this queries another wasm contract. You should know a priori the proper types for T and U
     // (response and request) based on the contract API
     pub fn query_wasm_smart<T: DeserializeOwned>(
@@ -244,24 +365,28 @@ impl<'a> QuerierWrapper<'a> {
         let raw = to_vec(&request).map_err(|serialize_err| {
             StdError::generic_err(format!("Serializing QueryRequest: {}", serialize_err))
         })?;
-        match self.raw_query(&raw) {
-            SystemResult::Err(system_err) => Err(StdError::generic_err(format!(
-                "Querier system error: {}",
-                system_err
-            ))),
-            SystemResult::Ok(ContractResult::Err(contract_err)) => Err(StdError::generic_err(
-                format!("Querier contract error: {}", contract_err),
-            )),
-            SystemResult::Ok(ContractResult::Ok(value)) => {
-                if value.is_empty() {
-                    Ok(None)
-                } else {
-                    Ok(Some(value.into()))
-                }
-            }
+        let value = map_query_result(self.raw_query(&raw))?;
+        if value.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(value.into()))
         }
     }
 
+    /// Returns metadata like code ID and admin of the contract at `contract_addr`.
+    /// If no contract exists at that address, this fails with a
+    /// `SystemError::NoSuchContract` wrapped in `StdError::QuerierSystemErr`.
+    pub fn query_wasm_contract_info(
+        &self,
+        contract_addr: impl Into<String>,
+    ) -> StdResult<ContractInfoResponse> {
+        let request = WasmQuery::ContractInfo {
+            contract_addr: contract_addr.into(),
+        }
+        .into();
+        self.query(&request)
+    }
+
     #[cfg(feature = "staking")]
     pub fn query_all_validators(&self) -> StdResult<Vec<Validator>> {
         let request = StakingQuery::AllValidators {}.into();
@@ -315,11 +440,24 @@ impl<'a> QuerierWrapper<'a> {
     }
 }
 
+/// Maps the two levels of a querier result into a `StdResult`, where a system error becomes
+/// `StdError::QuerierSystemErr` and a contract error becomes `StdError::QuerierContractErr`.
+fn map_query_result(result: QuerierResult) -> StdResult<Binary> {
+    match result {
+        SystemResult::Err(system_err) => Err(StdError::querier_system_err(system_err)),
+        SystemResult::Ok(ContractResult::Err(contract_err)) => {
+            Err(StdError::querier_contract_err(contract_err))
+        }
+        SystemResult::Ok(ContractResult::Ok(value)) => Ok(value),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::mock::MockQuerier;
-    use crate::{coins, from_slice, Uint128};
+    use crate::mock::{mock_dependencies_with_balances, MockQuerier};
+    use crate::{coin, coins, from_slice, SystemError, Uint128};
+    use serde::Deserialize;
 
     // this is a simple demo helper to prove we can use it
     fn demo_helper(_querier: &dyn Querier) -> u64 {
@@ -330,7 +468,7 @@ mod tests {
     #[test]
     fn use_querier_wrapper_as_querier() {
         let querier: MockQuerier<Empty> = MockQuerier::new(&[]);
-        let wrapper = QuerierWrapper::new(&querier);
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
 
         // call with deref shortcut
         let res = demo_helper(&*wrapper);
@@ -345,7 +483,7 @@ mod tests {
     fn auto_deref_raw_query() {
         let acct = String::from("foobar");
         let querier: MockQuerier<Empty> = MockQuerier::new(&[(&acct, &coins(5, "BTC"))]);
-        let wrapper = QuerierWrapper::new(&querier);
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
         let query = QueryRequest::<Empty>::Bank(BankQuery::Balance {
             address: acct,
             denom: "BTC".to_string(),
@@ -358,4 +496,343 @@ mod tests {
         let balance: BalanceResponse = from_slice(&raw).unwrap();
         assert_eq!(balance.amount.amount, Uint128::new(5));
     }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct CountResponse {
+        count: u32,
+    }
+
+    /// Serves state of a single contract for wasm queries
+    struct WasmQuerierStub;
+
+    impl Querier for WasmQuerierStub {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            let request: QueryRequest<Empty> = from_slice(bin_request).unwrap();
+            match request {
+                QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                    if contract_addr == "counter" =>
+                {
+                    // the counter contract answers with the length of the query
+                    let response = CountResponse {
+                        count: msg.len() as u32,
+                    };
+                    SystemResult::Ok(ContractResult::Ok(to_binary(&response).unwrap()))
+                }
+                QueryRequest::Wasm(WasmQuery::Smart { contract_addr, .. }) => {
+                    SystemResult::Err(SystemError::NoSuchContract {
+                        addr: contract_addr,
+                    })
+                }
+                QueryRequest::Wasm(WasmQuery::Raw { contract_addr, key })
+                    if contract_addr == "counter" =>
+                {
+                    // raw queries for missing keys return empty data
+                    let value = if key.as_slice() == b"count" {
+                        Binary::from(b"\x00\x07")
+                    } else {
+                        Binary::default()
+                    };
+                    SystemResult::Ok(ContractResult::Ok(value))
+                }
+                _ => panic!("unexpected query"),
+            }
+        }
+    }
+
+    #[test]
+    fn query_works() {
+        let querier: MockQuerier<Empty> = MockQuerier::new(&[("alice", &coins(5, "BTC"))]);
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+        let request = BankQuery::Balance {
+            address: "alice".to_string(),
+            denom: "BTC".to_string(),
+        }
+        .into();
+
+        let balance: BalanceResponse = wrapper.query(&request).unwrap();
+        assert_eq!(balance.amount, coin(5, "BTC"));
+    }
+
+    #[test]
+    fn query_returns_system_error() {
+        let querier: MockQuerier<Empty> = MockQuerier::new(&[]);
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+        let request = WasmQuery::Smart {
+            contract_addr: "nothing here".to_string(),
+            msg: to_binary(&Empty {}).unwrap(),
+        }
+        .into();
+
+        let err = wrapper.query::<Empty>(&request).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::querier_system_err(SystemError::NoSuchContract {
+                addr: "nothing here".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn query_returns_contract_error() {
+        let mut querier: MockQuerier<Empty> = MockQuerier::new(&[]);
+        querier
+            .update_wasm(|_| SystemResult::Ok(ContractResult::Err("not authorized".to_string())));
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+
+        let err = wrapper
+            .query_wasm_smart::<Empty>("guarded", &Empty {})
+            .unwrap_err();
+        assert_eq!(err, StdError::querier_contract_err("not authorized"));
+
+        let err = wrapper
+            .query_wasm_raw("guarded", b"key".to_vec())
+            .unwrap_err();
+        assert_eq!(err, StdError::querier_contract_err("not authorized"));
+    }
+
+    #[test]
+    fn query_returns_parse_error_with_target_type() {
+        let querier: MockQuerier<Empty> = MockQuerier::new(&[("alice", &coins(5, "BTC"))]);
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+        let request = BankQuery::Balance {
+            address: "alice".to_string(),
+            denom: "BTC".to_string(),
+        }
+        .into();
+
+        let err = wrapper.query::<AllBalanceResponse>(&request).unwrap_err();
+        match err {
+            StdError::ParseErr { target_type, .. } => {
+                assert_eq!(target_type, "cosmwasm_std::query::bank::AllBalanceResponse")
+            }
+            err => panic!("Unexpected error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn query_wasm_smart_works() {
+        let wrapper = QuerierWrapper::<Empty>::new(&WasmQuerierStub);
+
+        let msg = Empty {};
+        let response: CountResponse = wrapper.query_wasm_smart("counter", &msg).unwrap();
+        assert_eq!(
+            response,
+            CountResponse {
+                count: to_binary(&msg).unwrap().len() as u32
+            }
+        );
+
+        let err = wrapper
+            .query_wasm_smart::<CountResponse>("unknown", &msg)
+            .unwrap_err();
+        match err {
+            StdError::QuerierSystemErr {
+                source: SystemError::NoSuchContract { addr },
+                ..
+            } => assert_eq!(addr, "unknown"),
+            err => panic!("Unexpected error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn query_wasm_raw_works() {
+        let wrapper = QuerierWrapper::<Empty>::new(&WasmQuerierStub);
+
+        let value = wrapper
+            .query_wasm_raw("counter", b"count".to_vec())
+            .unwrap();
+        assert_eq!(value, Some(vec![0x00, 0x07]));
+
+        // missing key maps to None
+        let value = wrapper
+            .query_wasm_raw("counter", b"total".to_vec())
+            .unwrap();
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn query_wasm_contract_info_works() {
+        let mut querier: MockQuerier = MockQuerier::new(&[]);
+        querier.update_wasm(|request| match request {
+            WasmQuery::ContractInfo { contract_addr } if contract_addr == "counter" => {
+                let mut info = ContractInfoResponse::new(17, "creator");
+                info.admin = Some("admin".to_string());
+                SystemResult::Ok(ContractResult::Ok(to_binary(&info).unwrap()))
+            }
+            WasmQuery::ContractInfo { contract_addr }
+            | WasmQuery::Smart { contract_addr, .. }
+            | WasmQuery::Raw { contract_addr, .. } => {
+                SystemResult::Err(SystemError::NoSuchContract {
+                    addr: contract_addr.clone(),
+                })
+            }
+        });
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+
+        let info = wrapper.query_wasm_contract_info("counter").unwrap();
+        assert_eq!(info.code_id, 17);
+        assert_eq!(info.creator, "creator");
+        assert_eq!(info.admin, Some("admin".to_string()));
+        assert!(!info.pinned);
+
+        let err = wrapper.query_wasm_contract_info("nothing").unwrap_err();
+        assert_eq!(
+            err,
+            StdError::querier_system_err(SystemError::NoSuchContract {
+                addr: "nothing".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn query_batch_returns_result_per_request() {
+        let wrapper = QuerierWrapper::<Empty>::new(&WasmQuerierStub);
+
+        let requests: Vec<QueryRequest<Empty>> = vec![
+            WasmQuery::Smart {
+                contract_addr: "counter".to_string(),
+                msg: to_binary(&Empty {}).unwrap(),
+            }
+            .into(),
+            WasmQuery::Smart {
+                contract_addr: "nothing".to_string(),
+                msg: to_binary(&Empty {}).unwrap(),
+            }
+            .into(),
+            WasmQuery::Raw {
+                contract_addr: "counter".to_string(),
+                key: b"count".to_vec().into(),
+            }
+            .into(),
+        ];
+        let results = wrapper.query_batch(&requests).unwrap();
+        assert_eq!(results.len(), 3);
+
+        let response: CountResponse = from_binary(results[0].as_ref().unwrap()).unwrap();
+        assert_eq!(response, CountResponse { count: 2 });
+        match &results[1] {
+            Err(StdError::QuerierSystemErr { .. }) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert_eq!(results[2], Ok(Binary::from([0x00, 0x07])));
+
+        assert!(wrapper.query_batch(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn query_balance_works() {
+        let deps =
+            mock_dependencies_with_balances(&[("alice", &[coin(123, "ELF"), coin(777, "FLY")])]);
+        let balance = deps.as_ref().querier.query_balance("alice", "ELF").unwrap();
+        assert_eq!(balance, coin(123, "ELF"));
+
+        // unknown denom
+        let balance = deps.as_ref().querier.query_balance("alice", "BTC").unwrap();
+        assert_eq!(balance, coin(0, "BTC"));
+
+        // unknown address
+        let balance = deps.as_ref().querier.query_balance("bob", "ELF").unwrap();
+        assert_eq!(balance, coin(0, "ELF"));
+    }
+
+    #[test]
+    fn query_balance_required_works() {
+        let deps = mock_dependencies_with_balances(&[("alice", &[coin(123, "ELF")])]);
+        let querier = deps.as_ref().querier;
+
+        let balance = querier.query_balance_required("alice", "ELF").unwrap();
+        assert_eq!(balance, coin(123, "ELF"));
+
+        let err = querier.query_balance_required("bob", "ELF").unwrap_err();
+        assert_eq!(err, StdError::generic_err("No ELF balance for address bob"));
+        // the plain version still returns zero
+        let balance = querier.query_balance("bob", "ELF").unwrap();
+        assert_eq!(balance, coin(0, "ELF"));
+    }
+
+    #[test]
+    fn query_all_balances_works() {
+        let deps =
+            mock_dependencies_with_balances(&[("alice", &[coin(123, "ELF"), coin(777, "FLY")])]);
+        let balances = deps.as_ref().querier.query_all_balances("alice").unwrap();
+        assert_eq!(balances, vec![coin(123, "ELF"), coin(777, "FLY")]);
+
+        let balances = deps.as_ref().querier.query_all_balances("bob").unwrap();
+        assert_eq!(balances, vec![]);
+    }
+
+    #[cfg(feature = "staking")]
+    #[test]
+    fn staking_helpers_work() {
+        use crate::mock::mock_dependencies;
+        use crate::{Addr, Decimal};
+
+        let val1 = Validator {
+            address: String::from("validator-one"),
+            commission: Decimal::percent(1),
+            max_commission: Decimal::percent(3),
+            max_change_rate: Decimal::percent(1),
+        };
+        let val2 = Validator {
+            address: String::from("validator-two"),
+            commission: Decimal::permille(15),
+            max_commission: Decimal::permille(40),
+            max_change_rate: Decimal::permille(5),
+        };
+        let del1 = FullDelegation {
+            delegator: Addr::unchecked("alice"),
+            validator: val1.address.clone(),
+            amount: coin(100, "ustake"),
+            can_redelegate: coin(100, "ustake"),
+            accumulated_rewards: coins(5, "ustake"),
+        };
+
+        let mut deps = mock_dependencies(&[]);
+        deps.querier.update_staking(
+            "ustake",
+            &[val1.clone(), val2.clone()],
+            std::slice::from_ref(&del1),
+        );
+        let querier = deps.as_ref().querier;
+
+        assert_eq!(querier.query_bonded_denom().unwrap(), "ustake");
+        assert_eq!(
+            querier.query_all_validators().unwrap(),
+            vec![val1.clone(), val2]
+        );
+        assert_eq!(
+            querier.query_validator("validator-one").unwrap(),
+            Some(val1.clone())
+        );
+        assert_eq!(querier.query_validator("validator-three").unwrap(), None);
+
+        assert_eq!(
+            querier.query_all_delegations("alice").unwrap(),
+            vec![Delegation::from(del1.clone())]
+        );
+        assert_eq!(querier.query_all_delegations("bob").unwrap(), vec![]);
+        assert_eq!(
+            querier.query_delegation("alice", &val1.address).unwrap(),
+            Some(del1)
+        );
+        assert_eq!(
+            querier.query_delegation("alice", "validator-two").unwrap(),
+            None
+        );
+    }
+
+    #[cfg(feature = "bank_supply")]
+    #[test]
+    fn query_supply_works() {
+        let deps = mock_dependencies_with_balances(&[
+            ("alice", &[coin(123, "ELF"), coin(777, "FLY")]),
+            ("bob", &[coin(100, "ELF")]),
+        ]);
+        let supply = deps.as_ref().querier.query_supply("ELF").unwrap();
+        assert_eq!(supply, coin(223, "ELF"));
+
+        // unknown denom
+        let supply = deps.as_ref().querier.query_supply("BTC").unwrap();
+        assert_eq!(supply, coin(0, "BTC"));
+    }
 }
@@ -1,7 +1,7 @@
 use serde::{de::DeserializeOwned, ser::Serialize};
 use std::marker::PhantomData;
 
-use cosmwasm_std::{to_vec, StdError, StdResult, Storage};
+use cosmwasm_std::{to_vec, OverflowError, OverflowOperation, StdError, StdResult, Storage};
 #[cfg(feature = "iterator")]
 use cosmwasm_std::{Order, Pair};
 
@@ -10,8 +10,8 @@ use crate::length_prefixed::{to_length_prefixed, to_length_prefixed_nested};
 use crate::namespace_helpers::range_with_prefix;
 use crate::namespace_helpers::{get_with_prefix, remove_with_prefix, set_with_prefix};
 #[cfg(feature = "iterator")]
-use crate::type_helpers::deserialize_kv;
-use crate::type_helpers::{may_deserialize, must_deserialize};
+use crate::type_helpers::{deserialize_kv, deserialize_u64_kv};
+use crate::type_helpers::{may_deserialize, may_deserialize_or, must_deserialize, with_context};
 
 /// An alias of Bucket::new for less verbose usage
 pub fn bucket<'a, T>(storage: &'a mut dyn Storage, namespace: &[u8]) -> Bucket<'a, T>
@@ -61,7 +61,8 @@ where
 
     /// save will serialize the model and store, returns an error on serialization issues
     pub fn save(&mut self, key: &[u8], data: &T) -> StdResult<()> {
-        set_with_prefix(self.storage, &self.prefix, key, &to_vec(data)?);
+        let value = to_vec(data).map_err(|err| with_context(err, &self.prefix, key))?;
+        set_with_prefix(self.storage, &self.prefix, key, &value);
         Ok(())
     }
 
@@ -72,14 +73,25 @@ where
     /// load will return an error if no data is set at the given key, or on parse error
     pub fn load(&self, key: &[u8]) -> StdResult<T> {
         let value = get_with_prefix(self.storage, &self.prefix, key);
-        must_deserialize(&value)
+        must_deserialize(&value).map_err(|err| with_context(err, &self.prefix, key))
     }
 
     /// may_load will parse the data stored at the key if present, returns Ok(None) if no data there.
     /// returns an error on issues parsing
     pub fn may_load(&self, key: &[u8]) -> StdResult<Option<T>> {
         let value = get_with_prefix(self.storage, &self.prefix, key);
-        may_deserialize(&value)
+        may_deserialize(&value).map_err(|err| with_context(err, &self.prefix, key))
+    }
+
+    /// may_load_with_fallback works like may_load, but calls `fallback` with the raw stored bytes
+    /// if they cannot be parsed into `T`. Use this to lazily migrate values stored with an
+    /// older schema.
+    pub fn may_load_with_fallback<F>(&self, key: &[u8], fallback: F) -> StdResult<Option<T>>
+    where
+        F: FnOnce(&[u8]) -> StdResult<T>,
+    {
+        let value = get_with_prefix(self.storage, &self.prefix, key);
+        may_deserialize_or(&value, fallback).map_err(|err| with_context(err, &self.prefix, key))
     }
 
     #[cfg(feature = "iterator")]
@@ -90,7 +102,61 @@ where
         order: Order,
     ) -> Box<dyn Iterator<Item = StdResult<Pair<T>>> + 'b> {
         let mapped = range_with_prefix(self.storage, &self.prefix, start, end, order)
-            .map(deserialize_kv::<T>);
+            .map(move |kv| deserialize_kv::<T>(&self.prefix, kv));
+        Box::new(mapped)
+    }
+
+    /// find returns the first entry in the given order for which `predicate` returns true.
+    /// Iteration stops at the first match or the first parse error, so later entries are
+    /// not loaded.
+    #[cfg(feature = "iterator")]
+    pub fn find<F>(&self, order: Order, mut predicate: F) -> StdResult<Option<Pair<T>>>
+    where
+        F: FnMut(&Pair<T>) -> bool,
+    {
+        for item in self.range(None, None, order) {
+            let item = item?;
+            if predicate(&item) {
+                return Ok(Some(item));
+            }
+        }
+        Ok(None)
+    }
+
+    /// values is like range, but only returns the values and drops the keys
+    #[cfg(feature = "iterator")]
+    pub fn values<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = StdResult<T>> + 'b> {
+        let mapped = self
+            .range(start, end, order)
+            .map(|item| item.map(|(_, value)| value));
+        Box::new(mapped)
+    }
+
+    /// range_u64 iterates over entries stored under 8 byte big endian keys (see `u64::to_be_bytes`)
+    /// and returns the keys decoded as u64. Big endian encoding makes the byte order match the
+    /// numeric order. Entries with keys of a different length result in an error.
+    #[cfg(feature = "iterator")]
+    pub fn range_u64<'b>(
+        &'b self,
+        start: Option<u64>,
+        end: Option<u64>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = StdResult<(u64, T)>> + 'b> {
+        let start = start.map(u64::to_be_bytes);
+        let end = end.map(u64::to_be_bytes);
+        let mapped = range_with_prefix(
+            self.storage,
+            &self.prefix,
+            start.as_ref().map(|s| &s[..]),
+            end.as_ref().map(|e| &e[..]),
+            order,
+        )
+        .map(move |kv| deserialize_u64_kv::<T>(&self.prefix, kv));
         Box::new(mapped)
     }
 
@@ -110,6 +176,19 @@ where
     }
 }
 
+impl<'a> Bucket<'a, u64> {
+    /// increment adds `by` to the counter stored at the given key and returns the new value.
+    /// A missing counter is treated as 0. Returns an overflow error instead of wrapping around.
+    pub fn increment(&mut self, key: &[u8], by: u64) -> StdResult<u64> {
+        let current = self.may_load(key)?.unwrap_or_default();
+        let next = current.checked_add(by).ok_or_else(|| {
+            StdError::overflow(OverflowError::new(OverflowOperation::Add, current, by))
+        })?;
+        self.save(key, &next)?;
+        Ok(next)
+    }
+}
+
 pub struct ReadonlyBucket<'a, T>
 where
     T: Serialize + DeserializeOwned,
@@ -143,14 +222,25 @@ where
     /// load will return an error if no data is set at the given key, or on parse error
     pub fn load(&self, key: &[u8]) -> StdResult<T> {
         let value = get_with_prefix(self.storage, &self.prefix, key);
-        must_deserialize(&value)
+        must_deserialize(&value).map_err(|err| with_context(err, &self.prefix, key))
     }
 
     /// may_load will parse the data stored at the key if present, returns Ok(None) if no data there.
     /// returns an error on issues parsing
     pub fn may_load(&self, key: &[u8]) -> StdResult<Option<T>> {
         let value = get_with_prefix(self.storage, &self.prefix, key);
-        may_deserialize(&value)
+        may_deserialize(&value).map_err(|err| with_context(err, &self.prefix, key))
+    }
+
+    /// may_load_with_fallback works like may_load, but calls `fallback` with the raw stored bytes
+    /// if they cannot be parsed into `T`. Use this to lazily migrate values stored with an
+    /// older schema.
+    pub fn may_load_with_fallback<F>(&self, key: &[u8], fallback: F) -> StdResult<Option<T>>
+    where
+        F: FnOnce(&[u8]) -> StdResult<T>,
+    {
+        let value = get_with_prefix(self.storage, &self.prefix, key);
+        may_deserialize_or(&value, fallback).map_err(|err| with_context(err, &self.prefix, key))
     }
 
     #[cfg(feature = "iterator")]
@@ -161,7 +251,61 @@ where
         order: Order,
     ) -> Box<dyn Iterator<Item = StdResult<Pair<T>>> + 'b> {
         let mapped = range_with_prefix(self.storage, &self.prefix, start, end, order)
-            .map(deserialize_kv::<T>);
+            .map(move |kv| deserialize_kv::<T>(&self.prefix, kv));
+        Box::new(mapped)
+    }
+
+    /// find returns the first entry in the given order for which `predicate` returns true.
+    /// Iteration stops at the first match or the first parse error, so later entries are
+    /// not loaded.
+    #[cfg(feature = "iterator")]
+    pub fn find<F>(&self, order: Order, mut predicate: F) -> StdResult<Option<Pair<T>>>
+    where
+        F: FnMut(&Pair<T>) -> bool,
+    {
+        for item in self.range(None, None, order) {
+            let item = item?;
+            if predicate(&item) {
+                return Ok(Some(item));
+            }
+        }
+        Ok(None)
+    }
+
+    /// values is like range, but only returns the values and drops the keys
+    #[cfg(feature = "iterator")]
+    pub fn values<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = StdResult<T>> + 'b> {
+        let mapped = self
+            .range(start, end, order)
+            .map(|item| item.map(|(_, value)| value));
+        Box::new(mapped)
+    }
+
+    /// range_u64 iterates over entries stored under 8 byte big endian keys (see `u64::to_be_bytes`)
+    /// and returns the keys decoded as u64. Big endian encoding makes the byte order match the
+    /// numeric order. Entries with keys of a different length result in an error.
+    #[cfg(feature = "iterator")]
+    pub fn range_u64<'b>(
+        &'b self,
+        start: Option<u64>,
+        end: Option<u64>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = StdResult<(u64, T)>> + 'b> {
+        let start = start.map(u64::to_be_bytes);
+        let end = end.map(u64::to_be_bytes);
+        let mapped = range_with_prefix(
+            self.storage,
+            &self.prefix,
+            start.as_ref().map(|s| &s[..]),
+            end.as_ref().map(|e| &e[..]),
+            order,
+        )
+        .map(move |kv| deserialize_u64_kv::<T>(&self.prefix, kv));
         Box::new(mapped)
     }
 }
@@ -169,8 +313,10 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cosmwasm_std::testing::MockStorage;
-    use cosmwasm_std::StdError;
+    #[cfg(feature = "iterator")]
+    use crate::MeteredStorage;
+    use cosmwasm_std::testing::{mock_dependencies_with_storage, MockStorage};
+    use cosmwasm_std::{from_slice, StdError};
     use serde::{Deserialize, Serialize};
 
     #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
@@ -196,6 +342,51 @@ mod tests {
         assert_eq!(data, loaded);
     }
 
+    #[test]
+    fn increment_works() {
+        let mut store = MockStorage::new();
+        let mut counters = bucket::<u64>(&mut store, b"counters");
+
+        // first touch starts at 0
+        assert_eq!(counters.increment(b"minted", 5).unwrap(), 5);
+        assert_eq!(counters.increment(b"minted", 1).unwrap(), 6);
+        assert_eq!(counters.increment(b"minted", 0).unwrap(), 6);
+        assert_eq!(counters.load(b"minted").unwrap(), 6);
+
+        // counters are independent
+        assert_eq!(counters.increment(b"burned", 1).unwrap(), 1);
+        assert_eq!(counters.load(b"minted").unwrap(), 6);
+    }
+
+    #[test]
+    fn increment_errors_on_overflow() {
+        let mut store = MockStorage::new();
+        let mut counters = bucket::<u64>(&mut store, b"counters");
+        counters.save(b"id", &(u64::MAX - 1)).unwrap();
+
+        assert_eq!(counters.increment(b"id", 1).unwrap(), u64::MAX);
+        let err = counters.increment(b"id", 1).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::overflow(OverflowError::new(OverflowOperation::Add, u64::MAX, 1))
+        );
+        // value is unchanged
+        assert_eq!(counters.load(b"id").unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn load_from_seeded_storage() {
+        // keys are the length prefixed namespace followed by the key
+        let deps = mock_dependencies_with_storage(&[
+            (b"\x00\x04datamaria", br#"{"name":"Maria","age":42}"#),
+            (b"\x00\x04datajose", br#"{"name":"Jose","age":27}"#),
+        ]);
+
+        let bucket = bucket_read::<Data>(&deps.storage, b"data");
+        assert_eq!(bucket.load(b"maria").unwrap().age, 42);
+        assert_eq!(bucket.load(b"jose").unwrap().name, "Jose");
+    }
+
     #[test]
     fn remove_works() {
         let mut store = MockStorage::new();
@@ -218,6 +409,55 @@ mod tests {
         assert_eq!(None, bucket.may_load(b"maria").unwrap());
     }
 
+    #[test]
+    fn may_load_with_fallback_upgrades_legacy_data() {
+        #[derive(Serialize, Deserialize)]
+        struct DataV1 {
+            pub name: String,
+        }
+
+        let mut store = MockStorage::new();
+        let mut legacy = bucket::<DataV1>(&mut store, b"data");
+        legacy
+            .save(
+                b"maria",
+                &DataV1 {
+                    name: "Maria".to_string(),
+                },
+            )
+            .unwrap();
+
+        let upgrade = |raw: &[u8]| -> StdResult<Data> {
+            let old: DataV1 = from_slice(raw)?;
+            Ok(Data {
+                name: old.name,
+                age: 0,
+            })
+        };
+        let expected = Data {
+            name: "Maria".to_string(),
+            age: 0,
+        };
+
+        let mut bucket = bucket::<Data>(&mut store, b"data");
+        // plain loading fails on the old format
+        assert!(bucket.may_load(b"maria").is_err());
+        let loaded = bucket.may_load_with_fallback(b"maria", upgrade).unwrap();
+        assert_eq!(loaded, Some(expected.clone()));
+        assert_eq!(
+            bucket.may_load_with_fallback(b"john", upgrade).unwrap(),
+            None
+        );
+
+        // after saving, the new format is used
+        bucket.save(b"maria", &expected).unwrap();
+        let reader = bucket_read::<Data>(&store, b"data");
+        let loaded = reader
+            .may_load_with_fallback(b"maria", |_| panic!("must not be called"))
+            .unwrap();
+        assert_eq!(loaded, Some(expected));
+    }
+
     #[test]
     fn readonly_works() {
         let mut store = MockStorage::new();
@@ -457,4 +697,226 @@ mod tests {
         assert_eq!(data[0], (b"jose".to_vec(), jose));
         assert_eq!(data[1], (b"maria".to_vec(), maria));
     }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn values_works() {
+        let mut store = MockStorage::new();
+        let mut bucket = bucket::<Data>(&mut store, b"data");
+
+        let jose = Data {
+            name: "Jose".to_string(),
+            age: 42,
+        };
+        let maria = Data {
+            name: "Maria".to_string(),
+            age: 27,
+        };
+        let ben = Data {
+            name: "Ben".to_string(),
+            age: 11,
+        };
+        bucket.save(b"jose", &jose).unwrap();
+        bucket.save(b"maria", &maria).unwrap();
+        bucket.save(b"ben", &ben).unwrap();
+
+        let total: StdResult<i32> = bucket
+            .values(None, None, Order::Ascending)
+            .map(|item| item.map(|data| data.age))
+            .sum();
+        assert_eq!(total.unwrap(), 42 + 27 + 11);
+
+        // bounds work as in range
+        let names: Vec<String> = bucket
+            .values(Some(b"c"), None, Order::Descending)
+            .map(|item| item.unwrap().name)
+            .collect();
+        assert_eq!(names, vec!["Maria".to_string(), "Jose".to_string()]);
+
+        // same on a readonly bucket
+        let bucket = bucket_read::<Data>(&store, b"data");
+        let all: StdResult<Vec<Data>> = bucket.values(None, None, Order::Ascending).collect();
+        assert_eq!(all.unwrap(), vec![ben, jose, maria]);
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn find_stops_at_first_match() {
+        let mut store = MeteredStorage::new(MockStorage::new());
+        let mut bucket = bucket::<Data>(&mut store, b"data");
+        for i in 0..100u8 {
+            let data = Data {
+                name: format!("user{}", i),
+                age: i as i32,
+            };
+            bucket.save(&[i], &data).unwrap();
+        }
+
+        let found = bucket
+            .find(Order::Ascending, |(_, data)| data.age > 9)
+            .unwrap();
+        assert_eq!(
+            found,
+            Some((
+                vec![10],
+                Data {
+                    name: "user10".to_string(),
+                    age: 10
+                }
+            ))
+        );
+        // no match
+        let found = bucket.find(Order::Descending, |(_, data)| data.age > 100);
+        assert_eq!(found.unwrap(), None);
+
+        let bucket = bucket_read::<Data>(&store, b"data");
+        let (key, _) = bucket
+            .find(Order::Descending, |(_, data)| data.name.ends_with('5'))
+            .unwrap()
+            .unwrap();
+        assert_eq!(key, vec![95]);
+        // 11 entries read by the first search, 100 by the second, 5 by the third
+        assert_eq!(store.read_count(), 11 + 100 + 5);
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn range_u64_uses_numeric_order() {
+        let mut store = MockStorage::new();
+        let mut bucket = bucket::<u64>(&mut store, b"numbers");
+
+        // inserted out of order, around the 0xFF boundary
+        for key in &[256u64, 1, 255, u64::MAX, 0, 257] {
+            bucket.save(&key.to_be_bytes(), &(key / 2)).unwrap();
+        }
+
+        let all: StdResult<Vec<_>> = bucket.range_u64(None, None, Order::Ascending).collect();
+        assert_eq!(
+            all.unwrap(),
+            vec![
+                (0, 0),
+                (1, 0),
+                (255, 127),
+                (256, 128),
+                (257, 128),
+                (u64::MAX, u64::MAX / 2)
+            ]
+        );
+
+        // start is inclusive, end is exclusive
+        let keys: Vec<u64> = bucket
+            .range_u64(Some(255), Some(257), Order::Descending)
+            .map(|item| item.unwrap().0)
+            .collect();
+        assert_eq!(keys, vec![256, 255]);
+
+        // same on a readonly bucket
+        let bucket = bucket_read::<u64>(&store, b"numbers");
+        let keys: Vec<u64> = bucket
+            .range_u64(Some(2), None, Order::Ascending)
+            .map(|item| item.unwrap().0)
+            .collect();
+        assert_eq!(keys, vec![255, 256, 257, u64::MAX]);
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn range_u64_errors_for_other_key_lengths() {
+        let mut store = MockStorage::new();
+        let mut bucket = bucket::<u64>(&mut store, b"numbers");
+        bucket.save(b"short", &5).unwrap();
+
+        let err = bucket
+            .range_u64(None, None, Order::Ascending)
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err, StdError::invalid_data_size(8, 5));
+    }
+
+    /// A type that always fails to serialize
+    struct Unserializable;
+
+    impl Serialize for Unserializable {
+        fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("not today"))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Unserializable {
+        fn deserialize<D: serde::Deserializer<'de>>(_deserializer: D) -> Result<Self, D::Error> {
+            Ok(Unserializable)
+        }
+    }
+
+    #[test]
+    fn save_error_contains_namespace_and_key() {
+        let mut store = MockStorage::new();
+        let mut bucket = bucket::<Unserializable>(&mut store, b"data");
+
+        match bucket.save(b"maria", &Unserializable).unwrap_err() {
+            StdError::SerializeErr { msg, .. } => {
+                // hex of "\x00\x04data" and "maria"
+                assert!(
+                    msg.ends_with("not today (namespace: 000464617461, key: 6d61726961)"),
+                    "got: {}",
+                    msg
+                )
+            }
+            e => panic!("Unexpected error {}", e),
+        }
+        assert_eq!(store.get(b"\x00\x04datamaria"), None);
+    }
+
+    #[test]
+    fn load_error_contains_namespace_and_key() {
+        let mut store = MockStorage::new();
+        store.set(b"\x00\x04datajose", b"oops");
+
+        let bucket = bucket_read::<Data>(&store, b"data");
+        let expected = "(namespace: 000464617461, key: 6a6f7365)";
+        match bucket.load(b"jose").unwrap_err() {
+            StdError::ParseErr { msg, .. } => assert!(msg.ends_with(expected), "got: {}", msg),
+            e => panic!("Unexpected error {}", e),
+        }
+        match bucket.may_load(b"jose").unwrap_err() {
+            StdError::ParseErr { msg, .. } => assert!(msg.ends_with(expected), "got: {}", msg),
+            e => panic!("Unexpected error {}", e),
+        }
+        // not found errors are unchanged
+        assert_eq!(
+            bucket.load(b"maria").unwrap_err(),
+            StdError::not_found(std::any::type_name::<Data>())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn range_error_contains_namespace_and_key() {
+        let mut store = MockStorage::new();
+        let mut bucket = bucket::<Data>(&mut store, b"data");
+        let maria = Data {
+            name: "Maria".to_string(),
+            age: 27,
+        };
+        bucket.save(b"maria", &maria).unwrap();
+
+        // write corrupt data next to it
+        store.set(&[b"\x00\x04data".as_ref(), b"jose"].concat(), b"oops");
+
+        let read_bucket = bucket_read::<Data>(&store, b"data");
+        let mut iter = read_bucket.range(None, None, Order::Ascending);
+        match iter.next().unwrap().unwrap_err() {
+            StdError::ParseErr { msg, .. } => {
+                // hex of "\x00\x04data" and "jose"
+                assert!(
+                    msg.ends_with("(namespace: 000464617461, key: 6a6f7365)"),
+                    "got: {}",
+                    msg
+                )
+            }
+            e => panic!("Unexpected error {}", e),
+        }
+        assert_eq!(iter.next().unwrap().unwrap(), (b"maria".to_vec(), maria));
+    }
 }
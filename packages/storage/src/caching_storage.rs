@@ -0,0 +1,128 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use cosmwasm_std::Storage;
+#[cfg(feature = "iterator")]
+use cosmwasm_std::{Order, Pair};
+
+/// CachingStorage is a read-through cache in front of another storage.
+/// Results of `get` are memoized, so repeated reads of the same key only hit
+/// the underlying storage once. Writes and removals go straight to the underlying
+/// storage and update the cached entry.
+///
+/// Create it at the beginning of a message and drop it at the end. It must not be
+/// held across message boundaries, since it does not see changes made to the
+/// underlying storage by anyone else.
+pub struct CachingStorage<'a> {
+    storage: &'a mut dyn Storage,
+    cache: RefCell<HashMap<Vec<u8>, Option<Vec<u8>>>>,
+}
+
+impl<'a> CachingStorage<'a> {
+    pub fn new(storage: &'a mut dyn Storage) -> Self {
+        CachingStorage {
+            storage,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl<'a> Storage for CachingStorage<'a> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        if let Some(cached) = self.cache.borrow().get(key) {
+            return cached.clone();
+        }
+        let value = self.storage.get(key);
+        self.cache.borrow_mut().insert(key.to_vec(), value.clone());
+        value
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.storage.set(key, value);
+        self.cache
+            .get_mut()
+            .insert(key.to_vec(), Some(value.to_vec()));
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.storage.remove(key);
+        self.cache.get_mut().insert(key.to_vec(), None);
+    }
+
+    #[cfg(feature = "iterator")]
+    /// range is not cached and always reads from the underlying storage,
+    /// which already contains all writes
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Pair> + 'b> {
+        self.storage.range(start, end, order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MeteredStorage;
+    use cosmwasm_std::testing::MockStorage;
+
+    #[test]
+    fn get_reads_underlying_storage_once() {
+        let mut counting = MeteredStorage::new(MockStorage::new());
+        counting.set(b"foo", b"bar");
+
+        let cache = CachingStorage::new(&mut counting);
+        assert_eq!(cache.get(b"foo"), Some(b"bar".to_vec()));
+        assert_eq!(cache.get(b"foo"), Some(b"bar".to_vec()));
+        // missing keys are cached as well
+        assert_eq!(cache.get(b"food"), None);
+        assert_eq!(cache.get(b"food"), None);
+        drop(cache);
+
+        assert_eq!(counting.read_count(), 2);
+    }
+
+    #[test]
+    fn set_and_remove_update_cache() {
+        let mut counting = MeteredStorage::new(MockStorage::new());
+        counting.set(b"foo", b"bar");
+
+        let mut cache = CachingStorage::new(&mut counting);
+        assert_eq!(cache.get(b"foo"), Some(b"bar".to_vec()));
+
+        cache.set(b"foo", b"baz");
+        assert_eq!(cache.get(b"foo"), Some(b"baz".to_vec()));
+
+        cache.remove(b"foo");
+        assert_eq!(cache.get(b"foo"), None);
+
+        cache.set(b"new", b"value");
+        assert_eq!(cache.get(b"new"), Some(b"value".to_vec()));
+        drop(cache);
+
+        // only the very first read hit the underlying storage
+        assert_eq!(counting.read_count(), 1);
+        // and all writes went through
+        assert_eq!(counting.write_count(), 3);
+        assert_eq!(counting.remove_count(), 1);
+        let storage = counting.into_inner();
+        assert_eq!(storage.get(b"foo"), None);
+        assert_eq!(storage.get(b"new"), Some(b"value".to_vec()));
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn range_sees_writes() {
+        let mut storage = MockStorage::new();
+        storage.set(b"a", b"1");
+
+        let mut cache = CachingStorage::new(&mut storage);
+        cache.set(b"b", b"2");
+        cache.remove(b"a");
+
+        let all: Vec<Pair> = cache.range(None, None, Order::Ascending).collect();
+        assert_eq!(all, vec![(b"b".to_vec(), b"2".to_vec())]);
+    }
+}
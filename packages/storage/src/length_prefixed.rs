@@ -1,9 +1,11 @@
 //! This module is an implemention of a namespacing scheme described
 //! in https://github.com/webmaster128/key-namespacing#length-prefixed-keys
 //!
-//! Everything in this file is only responsible for building such keys
+//! Everything in this file is only responsible for building and parsing such keys
 //! and is in no way specific to any kind of storage.
 
+use cosmwasm_std::{StdError, StdResult};
+
 /// Calculates the raw key prefix for a given namespace as documented
 /// in https://github.com/webmaster128/key-namespacing#length-prefixed-keys
 pub fn to_length_prefixed(namespace: &[u8]) -> Vec<u8> {
@@ -16,17 +18,81 @@ pub fn to_length_prefixed(namespace: &[u8]) -> Vec<u8> {
 /// Calculates the raw key prefix for a given nested namespace
 /// as documented in https://github.com/webmaster128/key-namespacing#nesting
 pub fn to_length_prefixed_nested(namespaces: &[&[u8]]) -> Vec<u8> {
-    let mut size = 0;
-    for &namespace in namespaces {
-        size += namespace.len() + 2;
+    Namespace::nested(namespaces).into_vec()
+}
+
+/// Parses the first segment of length prefixed data as created by `to_length_prefixed`
+/// or `to_length_prefixed_nested`. Returns the segment and the remaining bytes after it.
+/// Errors if the data is too short for the length prefix or the segment.
+pub fn from_length_prefixed(bytes: &[u8]) -> StdResult<(Vec<u8>, &[u8])> {
+    if bytes.len() < 2 {
+        return Err(StdError::generic_err(
+            "Length prefixed data too short for the length prefix",
+        ));
+    }
+    let (length, rest) = bytes.split_at(2);
+    let length = u16::from_be_bytes([length[0], length[1]]) as usize;
+    if rest.len() < length {
+        return Err(StdError::generic_err(format!(
+            "Length prefixed data too short: segment length is {} but only {} bytes left",
+            length,
+            rest.len()
+        )));
+    }
+    let (segment, rest) = rest.split_at(length);
+    Ok((segment.to_vec(), rest))
+}
+
+/// A builder for nested namespaces as documented in
+/// https://github.com/webmaster128/key-namespacing#nesting
+///
+/// `Namespace::new(a).nest(b).nest(c)` produces the same bytes as
+/// `to_length_prefixed_nested(&[a, b, c])` without the need to get the slice shape right.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Namespace {
+    prefix: Vec<u8>,
+}
+
+impl Namespace {
+    /// Creates a namespace with a single level `root`
+    pub fn new(root: &[u8]) -> Self {
+        Namespace {
+            prefix: to_length_prefixed(root),
+        }
     }
 
-    let mut out = Vec::with_capacity(size);
-    for &namespace in namespaces {
-        out.extend_from_slice(&encode_length(namespace));
-        out.extend_from_slice(namespace);
+    /// Creates a namespace with one level per element of `namespaces`, outermost first.
+    /// An empty list results in an empty prefix.
+    pub fn nested(namespaces: &[&[u8]]) -> Self {
+        let mut size = 0;
+        for &namespace in namespaces {
+            size += namespace.len() + 2;
+        }
+
+        let mut prefix = Vec::with_capacity(size);
+        for &namespace in namespaces {
+            prefix.extend_from_slice(&encode_length(namespace));
+            prefix.extend_from_slice(namespace);
+        }
+        Namespace { prefix }
+    }
+
+    /// Appends one more nesting level
+    pub fn nest(mut self, namespace: &[u8]) -> Self {
+        self.prefix.extend_from_slice(&encode_length(namespace));
+        self.prefix.extend_from_slice(namespace);
+        self
+    }
+
+    /// Returns the raw key prefix
+    pub fn as_slice(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    /// Consumes the builder and returns the raw key prefix
+    pub fn into_vec(self) -> Vec<u8> {
+        self.prefix
     }
-    out
 }
 
 /// Encodes the length of a given namespace as a 2 byte big endian encoded integer
@@ -156,6 +222,102 @@ mod tests {
         assert_eq!(key.capacity(), key.len());
     }
 
+    #[test]
+    fn from_length_prefixed_works() {
+        let data = to_length_prefixed(b"foo");
+        let (segment, rest) = from_length_prefixed(&data).unwrap();
+        assert_eq!(segment, b"foo".to_vec());
+        assert_eq!(rest, b"");
+
+        let data = to_length_prefixed(b"");
+        let (segment, rest) = from_length_prefixed(&data).unwrap();
+        assert_eq!(segment, b"".to_vec());
+        assert_eq!(rest, b"");
+
+        // nested namespaces can be parsed one after another
+        let nested = to_length_prefixed_nested(&[b"a", b"bcd", b""]);
+        let (segment, rest) = from_length_prefixed(&nested).unwrap();
+        assert_eq!(segment, b"a".to_vec());
+        let (segment, rest) = from_length_prefixed(rest).unwrap();
+        assert_eq!(segment, b"bcd".to_vec());
+        let (segment, rest) = from_length_prefixed(rest).unwrap();
+        assert_eq!(segment, b"".to_vec());
+        assert_eq!(rest, b"");
+
+        // remaining key after the prefix is returned
+        let key = [to_length_prefixed(b"idx").as_slice(), b"pk"].concat();
+        let (segment, rest) = from_length_prefixed(&key).unwrap();
+        assert_eq!(segment, b"idx".to_vec());
+        assert_eq!(rest, b"pk");
+    }
+
+    #[test]
+    fn from_length_prefixed_errors_for_truncated_data() {
+        match from_length_prefixed(b"\x00").unwrap_err() {
+            StdError::GenericErr { msg, .. } => {
+                assert_eq!(msg, "Length prefixed data too short for the length prefix")
+            }
+            err => panic!("Unexpected error: {:?}", err),
+        }
+        assert!(from_length_prefixed(b"").is_err());
+
+        match from_length_prefixed(b"\x00\x04foo").unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert_eq!(
+                msg,
+                "Length prefixed data too short: segment length is 4 but only 3 bytes left"
+            ),
+            err => panic!("Unexpected error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn namespace_works() {
+        assert_eq!(Namespace::new(b"").as_slice(), b"\x00\x00");
+        assert_eq!(Namespace::new(b"a").as_slice(), to_length_prefixed(b"a"));
+        assert_eq!(
+            Namespace::new(b"a").nest(b"ab").as_slice(),
+            b"\x00\x01a\x00\x02ab"
+        );
+        assert_eq!(
+            Namespace::new(b"a").nest(b"").nest(b"").into_vec(),
+            b"\x00\x01a\x00\x00\x00\x00"
+        );
+    }
+
+    #[test]
+    fn namespace_nested_works() {
+        assert_eq!(Namespace::nested(&[]).as_slice(), b"");
+        assert_eq!(Namespace::nested(&[b"a"]), Namespace::new(b"a"));
+        assert_eq!(
+            Namespace::nested(&[b"a", b"ab", b""]),
+            Namespace::new(b"a").nest(b"ab").nest(b"")
+        );
+    }
+
+    #[test]
+    fn namespace_matches_to_length_prefixed_nested() {
+        assert_eq!(
+            Namespace::new(b"a").nest(b"ab").nest(b"abc").into_vec(),
+            to_length_prefixed_nested(&[b"a", b"ab", b"abc"])
+        );
+
+        let long_namespace1 = vec![0xaa; 0xFFFD];
+        let long_namespace2 = vec![0xbb; 0xFFFF];
+        assert_eq!(
+            Namespace::new(&long_namespace1)
+                .nest(&long_namespace2)
+                .nest(b"foo")
+                .into_vec(),
+            to_length_prefixed_nested(&[&long_namespace1, &long_namespace2, b"foo"])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "only supports namespaces up to length 0xFFFF")]
+    fn namespace_nest_panics_for_too_long_namespace() {
+        Namespace::new(b"a").nest(&vec![0; 0xFFFF + 1]);
+    }
+
     #[test]
     fn encode_length_works() {
         assert_eq!(encode_length(b""), *b"\x00\x00");
@@ -1,13 +1,31 @@
 mod bucket;
+mod caching_storage;
 mod length_prefixed;
+mod metered_storage;
 mod namespace_helpers;
+mod namespace_registry;
 mod prefixed_storage;
+mod queue;
 mod sequence;
 mod singleton;
+mod storage_ext;
+mod transactional_storage;
 mod type_helpers;
 
 pub use bucket::{bucket, bucket_read, Bucket, ReadonlyBucket};
-pub use length_prefixed::{to_length_prefixed, to_length_prefixed_nested};
-pub use prefixed_storage::{prefixed, prefixed_read, PrefixedStorage, ReadonlyPrefixedStorage};
+pub use caching_storage::CachingStorage;
+pub use length_prefixed::{
+    from_length_prefixed, to_length_prefixed, to_length_prefixed_nested, Namespace,
+};
+pub use metered_storage::MeteredStorage;
+#[cfg(feature = "iterator")]
+pub use namespace_helpers::remove_prefix;
+pub use namespace_registry::register_namespace;
+pub use prefixed_storage::{
+    prefixed, prefixed_read, with_prefixed_storage, PrefixedStorage, ReadonlyPrefixedStorage,
+};
+pub use queue::{queue, queue_read, Queue, ReadonlyQueue};
 pub use sequence::{currval, nextval, sequence};
 pub use singleton::{singleton, singleton_read, ReadonlySingleton, Singleton};
+pub use storage_ext::StorageExt;
+pub use transactional_storage::TransactionalStorage;
@@ -0,0 +1,170 @@
+use std::cell::Cell;
+
+use cosmwasm_std::Storage;
+#[cfg(feature = "iterator")]
+use cosmwasm_std::{Order, Pair};
+
+/// MeteredStorage counts the storage operations performed through it.
+///
+/// It owns the wrapped storage, so it can be used wherever a `Storage` value is needed,
+/// e.g. in `OwnedDeps`. Bytes are counted as key length plus value length.
+/// Each item returned by `range` is counted as one read.
+///
+/// This is meant for tests and benchmarks. The counters are not related to gas
+/// consumption on chain.
+#[derive(Default)]
+pub struct MeteredStorage<S: Storage> {
+    storage: S,
+    read_count: Cell<u64>,
+    read_bytes: Cell<u64>,
+    write_count: u64,
+    write_bytes: u64,
+    remove_count: u64,
+}
+
+impl<S: Storage> MeteredStorage<S> {
+    pub fn new(storage: S) -> Self {
+        MeteredStorage {
+            storage,
+            read_count: Cell::new(0),
+            read_bytes: Cell::new(0),
+            write_count: 0,
+            write_bytes: 0,
+            remove_count: 0,
+        }
+    }
+
+    /// Number of values read via `get` or `range`, including reads of missing keys
+    pub fn read_count(&self) -> u64 {
+        self.read_count.get()
+    }
+
+    pub fn read_bytes(&self) -> u64 {
+        self.read_bytes.get()
+    }
+
+    pub fn write_count(&self) -> u64 {
+        self.write_count
+    }
+
+    pub fn write_bytes(&self) -> u64 {
+        self.write_bytes
+    }
+
+    pub fn remove_count(&self) -> u64 {
+        self.remove_count
+    }
+
+    /// Returns the wrapped storage, dropping the counters
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+
+    fn count_read(&self, bytes: usize) {
+        self.read_count.set(self.read_count.get() + 1);
+        self.read_bytes.set(self.read_bytes.get() + bytes as u64);
+    }
+}
+
+impl<S: Storage> Storage for MeteredStorage<S> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let value = self.storage.get(key);
+        self.count_read(key.len() + value.as_ref().map(|v| v.len()).unwrap_or_default());
+        value
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.write_count += 1;
+        self.write_bytes += (key.len() + value.len()) as u64;
+        self.storage.set(key, value);
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.remove_count += 1;
+        self.storage.remove(key);
+    }
+
+    #[cfg(feature = "iterator")]
+    fn range<'a>(
+        &'a self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Pair> + 'a> {
+        let iter = self.storage.range(start, end, order).map(move |(k, v)| {
+            self.count_read(k.len() + v.len());
+            (k, v)
+        });
+        Box::new(iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, MockStorage, MOCK_CONTRACT_ADDR};
+    use cosmwasm_std::{coins, Coin};
+
+    #[test]
+    fn counts_known_sequence() {
+        let mut storage = MeteredStorage::new(MockStorage::new());
+
+        storage.set(b"foo", b"bar");
+        storage.set(b"hello", b"world");
+        assert_eq!(storage.get(b"foo"), Some(b"bar".to_vec()));
+        assert_eq!(storage.get(b"missing"), None);
+        storage.remove(b"foo");
+
+        assert_eq!(storage.write_count(), 2);
+        assert_eq!(storage.write_bytes(), 6 + 10);
+        assert_eq!(storage.read_count(), 2);
+        assert_eq!(storage.read_bytes(), 6 + 7);
+        assert_eq!(storage.remove_count(), 1);
+
+        let inner = storage.into_inner();
+        assert_eq!(inner.get(b"hello"), Some(b"world".to_vec()));
+        assert_eq!(inner.get(b"foo"), None);
+    }
+
+    #[test]
+    fn works_in_owned_deps() {
+        let mut deps = mock_dependencies(&coins(5, "ATOM"));
+        deps.storage.set(b"foo", b"bar");
+
+        let mut deps = deps.map_storage(MeteredStorage::new);
+        {
+            let deps_mut = deps.as_mut();
+            assert_eq!(deps_mut.storage.get(b"foo"), Some(b"bar".to_vec()));
+            deps_mut.storage.set(b"foo", b"baz");
+            let balance = deps_mut
+                .querier
+                .query_balance(MOCK_CONTRACT_ADDR, "ATOM")
+                .unwrap();
+            assert_eq!(balance, Coin::new(5, "ATOM"));
+        }
+
+        assert_eq!(deps.storage.read_count(), 1);
+        assert_eq!(deps.storage.write_count(), 1);
+        assert_eq!(deps.storage.get(b"foo"), Some(b"baz".to_vec()));
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn counts_range_items() {
+        let mut storage = MeteredStorage::new(MockStorage::new());
+        storage.set(b"a", b"1");
+        storage.set(b"b", b"22");
+        storage.set(b"c", b"333");
+
+        // iterators are lazy, only consumed items are counted
+        let first = storage.range(None, None, Order::Ascending).next();
+        assert_eq!(first, Some((b"a".to_vec(), b"1".to_vec())));
+        assert_eq!(storage.read_count(), 1);
+        assert_eq!(storage.read_bytes(), 2);
+
+        let all: Vec<Pair> = storage.range(None, None, Order::Descending).collect();
+        assert_eq!(all.len(), 3);
+        assert_eq!(storage.read_count(), 4);
+        assert_eq!(storage.read_bytes(), 2 + 2 + 3 + 4);
+    }
+}
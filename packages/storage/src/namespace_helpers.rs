@@ -44,13 +44,13 @@ pub(crate) fn range_with_prefix<'a>(
         None => namespace.to_vec(),
     };
     let end = match end {
-        Some(e) => concat(namespace, e),
-        // end is updating last byte by one
-        None => namespace_upper_bound(namespace),
+        Some(e) => Some(concat(namespace, e)),
+        // end is the first key after all keys starting with namespace
+        None => next_key(namespace),
     };
 
     // get iterator from storage
-    let base_iterator = storage.range(Some(&start), Some(&end), order);
+    let base_iterator = storage.range(Some(&start), end.as_deref(), order);
 
     // make a copy for the closure to handle lifetimes safely
     let prefix = namespace.to_vec();
@@ -58,28 +58,37 @@ pub(crate) fn range_with_prefix<'a>(
     Box::new(mapped)
 }
 
+/// Removes all keys starting with `prefix` from storage.
+///
+/// To delete all entries of a bucket or prefixed storage, pass the length prefixed
+/// namespace (see `to_length_prefixed`).
+#[cfg(feature = "iterator")]
+pub fn remove_prefix(storage: &mut dyn Storage, prefix: &[u8]) {
+    // collect keys first, since we cannot modify storage while iterating over it
+    let keys: Vec<Vec<u8>> = range_with_prefix(storage, prefix, None, None, Order::Ascending)
+        .map(|(key, _)| key)
+        .collect();
+    for key in keys {
+        remove_with_prefix(storage, prefix, &key);
+    }
+}
+
 #[cfg(feature = "iterator")]
 #[inline]
 fn trim(namespace: &[u8], key: &[u8]) -> Vec<u8> {
     key[namespace.len()..].to_vec()
 }
 
-/// Returns a new vec of same length and last byte incremented by one
-/// If last bytes are 255, we handle overflow up the chain.
-/// If all bytes are 255, this returns wrong data - but that is never possible as a namespace
+/// Returns the lexicographically smallest key that is greater than all keys starting with `prefix`.
+/// This is `prefix` with trailing 255 bytes removed and the last remaining byte incremented by one.
+/// Returns None if there is no such key (`prefix` is empty or all bytes are 255), which means
+/// the range is unbounded.
 #[cfg(feature = "iterator")]
-fn namespace_upper_bound(input: &[u8]) -> Vec<u8> {
-    let mut copy = input.to_vec();
-    // zero out all trailing 255, increment first that is not such
-    for i in (0..input.len()).rev() {
-        if copy[i] == 255 {
-            copy[i] = 0;
-        } else {
-            copy[i] += 1;
-            break;
-        }
-    }
-    copy
+pub(crate) fn next_key(prefix: &[u8]) -> Option<Vec<u8>> {
+    let last = prefix.iter().rposition(|&byte| byte != 255)?;
+    let mut next = prefix[..=last].to_vec();
+    next[last] += 1;
+    Some(next)
 }
 
 #[cfg(test)]
@@ -136,6 +145,60 @@ mod tests {
         assert_eq!(first, (expected_key, b"none".to_vec()));
     }
 
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn range_orders_keys_of_different_lengths_bytewise() {
+        let mut storage = MockStorage::new();
+        let prefix = to_length_prefixed(b"foo");
+
+        // keys of differing lengths, inserted out of order
+        for key in &[
+            &b"b"[..],
+            b"aa",
+            b"\xff",
+            b"a",
+            b"ab\x00",
+            b"ab",
+            b"\x00\x01",
+        ] {
+            set_with_prefix(&mut storage, &prefix, key, b"1");
+        }
+        // a longer namespace starting with the same bytes must not leak in
+        set_with_prefix(&mut storage, &to_length_prefixed(b"fooo"), b"a", b"2");
+
+        // keys are sorted bytewise, i.e. a key comes before all its extensions,
+        // independent of its length
+        let expected: Vec<Vec<u8>> = vec![
+            b"\x00\x01".to_vec(),
+            b"a".to_vec(),
+            b"aa".to_vec(),
+            b"ab".to_vec(),
+            b"ab\x00".to_vec(),
+            b"b".to_vec(),
+            b"\xff".to_vec(),
+        ];
+        let keys: Vec<Vec<u8>> = range_with_prefix(&storage, &prefix, None, None, Order::Ascending)
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, expected);
+
+        let keys: Vec<Vec<u8>> =
+            range_with_prefix(&storage, &prefix, None, None, Order::Descending)
+                .map(|(k, _)| k)
+                .collect();
+        let mut reversed = expected.clone();
+        reversed.reverse();
+        assert_eq!(keys, reversed);
+
+        // this is the same order as the raw storage
+        let raw: Vec<Vec<u8>> = storage
+            .range(None, None, Order::Ascending)
+            .filter(|(k, _)| k.starts_with(&prefix))
+            .map(|(k, _)| trim(&prefix, &k))
+            .collect();
+        assert_eq!(raw, expected);
+    }
+
     #[test]
     #[cfg(feature = "iterator")]
     fn range_with_prefix_wrapover() {
@@ -205,16 +268,80 @@ mod tests {
 
     #[test]
     #[cfg(feature = "iterator")]
-    fn namespace_upper_bound_works() {
-        assert_eq!(namespace_upper_bound(b"bob"), b"boc".to_vec());
-        assert_eq!(namespace_upper_bound(b"fo\xfe"), b"fo\xff".to_vec());
-        assert_eq!(namespace_upper_bound(b"fo\xff"), b"fp\x00".to_vec());
+    fn next_key_works() {
+        assert_eq!(next_key(b"bob"), Some(b"boc".to_vec()));
+        assert_eq!(next_key(b"fo\xfe"), Some(b"fo\xff".to_vec()));
+        assert_eq!(next_key(b"fo\xff"), Some(b"fp".to_vec()));
         // multiple \xff roll over
+        assert_eq!(next_key(b"fo\xff\xff\xff"), Some(b"fp".to_vec()));
+        // \xff not at the end are ignored
+        assert_eq!(next_key(b"\xffabc"), Some(b"\xffabd".to_vec()));
+
+        assert_eq!(next_key(&[0x00]), Some(vec![0x01]));
+        assert_eq!(next_key(&[0x01, 0xFF]), Some(vec![0x02]));
+        // no upper bound
+        assert_eq!(next_key(&[0xFF]), None);
+        assert_eq!(next_key(&[0xFF, 0xFF]), None);
+        assert_eq!(next_key(&[]), None);
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn remove_prefix_works() {
+        let mut storage = MockStorage::new();
+        let prefix = to_length_prefixed(b"foo");
+        let other_prefix = to_length_prefixed(b"food");
+
+        for i in 0u8..10 {
+            set_with_prefix(&mut storage, &prefix, &[i], b"value");
+        }
+        set_with_prefix(&mut storage, &other_prefix, b"bar", b"other");
+        storage.set(b"foo", b"no prefix");
+
+        remove_prefix(&mut storage, &prefix);
+
+        let left: Vec<Pair> =
+            range_with_prefix(&storage, &prefix, None, None, Order::Ascending).collect();
+        assert_eq!(left, vec![]);
+        // keys outside of the prefix survive
         assert_eq!(
-            namespace_upper_bound(b"fo\xff\xff\xff"),
-            b"fp\x00\x00\x00".to_vec()
+            get_with_prefix(&storage, &other_prefix, b"bar"),
+            Some(b"other".to_vec())
+        );
+        assert_eq!(storage.get(b"foo"), Some(b"no prefix".to_vec()));
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn range_with_prefix_ending_in_255() {
+        let mut storage = MockStorage::new();
+        storage.set(b"fo", b"before");
+        storage.set(b"fo\xff", b"empty key");
+        storage.set(b"fo\xff\xffbar", b"in");
+        storage.set(b"fp", b"after");
+        storage.set(b"fp\x00", b"after");
+
+        let all: Vec<Pair> =
+            range_with_prefix(&storage, b"fo\xff", None, None, Order::Ascending).collect();
+        assert_eq!(
+            all,
+            vec![
+                (b"".to_vec(), b"empty key".to_vec()),
+                (b"\xffbar".to_vec(), b"in".to_vec()),
+            ]
+        );
+
+        // a prefix of only \xff is unbounded at the end
+        storage.set(b"\xff\xff", b"in");
+        storage.set(b"\xff\xff\xff\xff", b"in too");
+        let all: Vec<Pair> =
+            range_with_prefix(&storage, b"\xff\xff", None, None, Order::Descending).collect();
+        assert_eq!(
+            all,
+            vec![
+                (b"\xff\xff".to_vec(), b"in too".to_vec()),
+                (b"".to_vec(), b"in".to_vec()),
+            ]
         );
-        // \xff not at the end are ignored
-        assert_eq!(namespace_upper_bound(b"\xffabc"), b"\xffabd".to_vec());
     }
 }
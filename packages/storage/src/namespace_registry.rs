@@ -0,0 +1,106 @@
+#[cfg(debug_assertions)]
+use lazy_static::lazy_static;
+#[cfg(debug_assertions)]
+use std::collections::HashMap;
+#[cfg(debug_assertions)]
+use std::sync::Mutex;
+
+/// Returns the given string literal as a namespace for buckets, singletons and prefixed storages.
+///
+/// In debug builds (e.g. when running tests) every namespace created this way is registered
+/// together with the location of the macro call. Using the same namespace at two different
+/// places panics, since two storage types sharing a namespace overwrite each other's data.
+/// Calling the same line multiple times is fine. The registry is shared by all threads of the
+/// process, so duplicates are also found across tests running in parallel. Release builds
+/// perform no checks.
+///
+/// # Example
+///
+/// ```
+/// # use cosmwasm_std::testing::MockStorage;
+/// use cosmwasm_storage::{bucket, namespace};
+///
+/// let mut storage = MockStorage::new();
+/// let mut orders = bucket::<u64>(&mut storage, namespace!("orders"));
+/// orders.save(b"alice", &5).unwrap();
+/// ```
+#[macro_export]
+macro_rules! namespace {
+    ($name:literal) => {
+        $crate::register_namespace($name, concat!(file!(), ":", line!(), ":", column!()))
+    };
+}
+
+#[cfg(debug_assertions)]
+lazy_static! {
+    static ref NAMESPACES: Mutex<HashMap<&'static str, &'static str>> = Mutex::new(HashMap::new());
+}
+
+/// Implementation of the `namespace!` macro. Not meant to be called directly.
+#[doc(hidden)]
+pub fn register_namespace(name: &'static str, location: &'static str) -> &'static [u8] {
+    #[cfg(debug_assertions)]
+    {
+        // A poisoned lock only means another caller panicked below, the map itself is intact
+        let mut namespaces = NAMESPACES
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let existing = *namespaces.entry(name).or_insert(location);
+        // release the lock before panicking, such that other threads can continue
+        drop(namespaces);
+        if existing != location {
+            panic!(
+                "Namespace \"{}\" used at {} was already used at {}",
+                name, location, existing
+            );
+        }
+    }
+    #[cfg(not(debug_assertions))]
+    let _ = location;
+    name.as_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bucket, bucket_read};
+    use cosmwasm_std::testing::MockStorage;
+
+    fn orders_namespace() -> &'static [u8] {
+        namespace!("orders")
+    }
+
+    #[test]
+    fn namespace_can_be_reused_from_same_location() {
+        let mut storage = MockStorage::new();
+        assert_eq!(orders_namespace(), b"orders");
+
+        bucket::<u64>(&mut storage, orders_namespace())
+            .save(b"alice", &5)
+            .unwrap();
+        let loaded = bucket_read::<u64>(&storage, orders_namespace())
+            .load(b"alice")
+            .unwrap();
+        assert_eq!(loaded, 5);
+
+        // different namespaces do not conflict
+        assert_eq!(namespace!("fills"), b"fills");
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Namespace \"duplicates\" used at")]
+    fn namespace_panics_for_duplicates() {
+        let mut storage = MockStorage::new();
+        let mut orders = bucket::<u64>(&mut storage, namespace!("duplicates"));
+        orders.save(b"alice", &5).unwrap();
+        let _fills = bucket::<u64>(&mut storage, namespace!("duplicates"));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Namespace \"threads\" used at")]
+    fn namespace_panics_for_duplicates_across_threads() {
+        std::thread::spawn(|| namespace!("threads")).join().unwrap();
+        namespace!("threads");
+    }
+}
@@ -1,8 +1,8 @@
-use cosmwasm_std::Storage;
+use cosmwasm_std::{CustomQuery, DepsMut, Storage};
 #[cfg(feature = "iterator")]
 use cosmwasm_std::{Order, Pair};
 
-use crate::length_prefixed::{to_length_prefixed, to_length_prefixed_nested};
+use crate::length_prefixed::{to_length_prefixed, Namespace};
 #[cfg(feature = "iterator")]
 use crate::namespace_helpers::range_with_prefix;
 use crate::namespace_helpers::{get_with_prefix, remove_with_prefix, set_with_prefix};
@@ -20,6 +20,24 @@ pub fn prefixed_read<'a>(
     ReadonlyPrefixedStorage::new(storage, namespace)
 }
 
+/// Runs `f` with dependencies whose storage is a PrefixedStorage for `namespace`.
+/// The api and querier are passed through unchanged.
+pub fn with_prefixed_storage<C: CustomQuery, F, R>(
+    deps: &mut DepsMut<C>,
+    namespace: &[u8],
+    f: F,
+) -> R
+where
+    F: FnOnce(DepsMut<C>) -> R,
+{
+    let mut storage = PrefixedStorage::new(deps.storage, namespace);
+    f(DepsMut {
+        storage: &mut storage,
+        api: deps.api,
+        querier: deps.querier,
+    })
+}
+
 pub struct PrefixedStorage<'a> {
     storage: &'a mut dyn Storage,
     prefix: Vec<u8>,
@@ -38,7 +56,15 @@ impl<'a> PrefixedStorage<'a> {
     pub fn multilevel(storage: &'a mut dyn Storage, namespaces: &[&[u8]]) -> Self {
         PrefixedStorage {
             storage,
-            prefix: to_length_prefixed_nested(namespaces),
+            prefix: Namespace::nested(namespaces).into_vec(),
+        }
+    }
+
+    /// Creates a prefixed storage for a namespace built with the `Namespace` builder
+    pub fn from_namespace(storage: &'a mut dyn Storage, namespace: &Namespace) -> Self {
+        PrefixedStorage {
+            storage,
+            prefix: namespace.as_slice().to_vec(),
         }
     }
 }
@@ -87,7 +113,15 @@ impl<'a> ReadonlyPrefixedStorage<'a> {
     pub fn multilevel(storage: &'a dyn Storage, namespaces: &[&[u8]]) -> Self {
         ReadonlyPrefixedStorage {
             storage,
-            prefix: to_length_prefixed_nested(namespaces),
+            prefix: Namespace::nested(namespaces).into_vec(),
+        }
+    }
+
+    /// Creates a prefixed storage for a namespace built with the `Namespace` builder
+    pub fn from_namespace(storage: &'a dyn Storage, namespace: &Namespace) -> Self {
+        ReadonlyPrefixedStorage {
+            storage,
+            prefix: namespace.as_slice().to_vec(),
         }
     }
 }
@@ -120,7 +154,7 @@ impl<'a> Storage for ReadonlyPrefixedStorage<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::testing::{mock_dependencies, MockStorage};
 
     #[test]
     fn prefixed_storage_set_and_get() {
@@ -155,6 +189,72 @@ mod tests {
         assert_eq!(bar.get(b"elsewhere"), None);
     }
 
+    #[test]
+    fn prefixed_storage_matches_namespace_helpers() {
+        let mut storage = MockStorage::new();
+
+        // write through the wrapper
+        let mut s1 = PrefixedStorage::new(&mut storage, b"foo");
+        s1.set(b"bar", b"gotcha");
+        s1.set(b"baz", b"winner");
+        s1.remove(b"baz");
+
+        // read through the raw helpers
+        let prefix = to_length_prefixed(b"foo");
+        assert_eq!(
+            get_with_prefix(&storage, &prefix, b"bar"),
+            Some(b"gotcha".to_vec())
+        );
+        assert_eq!(get_with_prefix(&storage, &prefix, b"baz"), None);
+
+        // and the other way round
+        set_with_prefix(&mut storage, &prefix, b"qux", b"raw");
+        let s2 = ReadonlyPrefixedStorage::new(&storage, b"foo");
+        assert_eq!(s2.get(b"qux"), Some(b"raw".to_vec()));
+    }
+
+    #[test]
+    fn prefixed_storage_from_namespace_set_and_get() {
+        let mut storage = MockStorage::new();
+
+        // set
+        let namespace = Namespace::new(b"foo").nest(b"bar");
+        let mut bar = PrefixedStorage::from_namespace(&mut storage, &namespace);
+        bar.set(b"baz", b"winner");
+        assert_eq!(
+            storage.get(b"\x00\x03foo\x00\x03barbaz").unwrap(),
+            b"winner".to_vec()
+        );
+
+        // get
+        let bar = ReadonlyPrefixedStorage::from_namespace(&storage, &namespace);
+        assert_eq!(bar.get(b"baz"), Some(b"winner".to_vec()));
+        let bar = ReadonlyPrefixedStorage::multilevel(&storage, &[b"foo", b"bar"]);
+        assert_eq!(bar.get(b"baz"), Some(b"winner".to_vec()));
+    }
+
+    #[test]
+    fn with_prefixed_storage_works() {
+        let mut deps = mock_dependencies(&[]);
+        let mut deps_mut = deps.as_mut();
+
+        let result = with_prefixed_storage(&mut deps_mut, b"foo", |deps| {
+            deps.storage.set(b"bar", b"gotcha");
+            assert_eq!(deps.storage.get(b"bar"), Some(b"gotcha".to_vec()));
+            // api is still available
+            deps.api.addr_validate("someone").unwrap();
+            42
+        });
+        assert_eq!(result, 42);
+
+        // the key landed under the prefix
+        assert_eq!(deps_mut.storage.get(b"bar"), None);
+        assert_eq!(
+            deps_mut.storage.get(b"\x00\x03foobar"),
+            Some(b"gotcha".to_vec())
+        );
+    }
+
     #[test]
     fn readonly_prefixed_storage_get() {
         let mut storage = MockStorage::new();
@@ -0,0 +1,275 @@
+use serde::{de::DeserializeOwned, ser::Serialize};
+use std::marker::PhantomData;
+
+use cosmwasm_std::{to_vec, StdError, StdResult, Storage};
+
+use crate::length_prefixed::to_length_prefixed;
+use crate::namespace_helpers::{get_with_prefix, remove_with_prefix, set_with_prefix};
+use crate::type_helpers::{may_deserialize, must_deserialize};
+
+// Keys of the pointers. Those are shorter than the 8 byte value keys and cannot collide.
+const HEAD_KEY: &[u8] = b"head";
+const TAIL_KEY: &[u8] = b"tail";
+
+/// An alias of Queue::new for less verbose usage
+pub fn queue<'a, T>(storage: &'a mut dyn Storage, namespace: &[u8]) -> Queue<'a, T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    Queue::new(storage, namespace)
+}
+
+/// An alias of ReadonlyQueue::new for less verbose usage
+pub fn queue_read<'a, T>(storage: &'a dyn Storage, namespace: &[u8]) -> ReadonlyQueue<'a, T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    ReadonlyQueue::new(storage, namespace)
+}
+
+/// Queue is a first in, first out list of elements stored under a namespace.
+///
+/// Elements are stored under their position encoded as 8 byte big endian keys.
+/// Two pointers store the position of the first element (head) and the position
+/// after the last element (tail).
+pub struct Queue<'a, T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    storage: &'a mut dyn Storage,
+    prefix: Vec<u8>,
+    // see https://doc.rust-lang.org/std/marker/struct.PhantomData.html#unused-type-parameters for why this is needed
+    data: PhantomData<T>,
+}
+
+impl<'a, T> Queue<'a, T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    pub fn new(storage: &'a mut dyn Storage, namespace: &[u8]) -> Self {
+        Queue {
+            storage,
+            prefix: to_length_prefixed(namespace),
+            data: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements in the queue
+    pub fn len(&self) -> StdResult<u64> {
+        Ok(self.tail()? - self.head()?)
+    }
+
+    pub fn is_empty(&self) -> StdResult<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Adds an element to the end of the queue
+    pub fn push_back(&mut self, data: &T) -> StdResult<()> {
+        let tail = self.tail()?;
+        let next = tail
+            .checked_add(1)
+            .ok_or_else(|| StdError::generic_err("Queue position overflow"))?;
+        set_with_prefix(
+            self.storage,
+            &self.prefix,
+            &tail.to_be_bytes(),
+            &to_vec(data)?,
+        );
+        self.set_pointer(TAIL_KEY, next)
+    }
+
+    /// Removes the first element of the queue and returns it, or returns None if the queue is empty
+    pub fn pop_front(&mut self) -> StdResult<Option<T>> {
+        let head = self.head()?;
+        let tail = self.tail()?;
+        if head == tail {
+            return Ok(None);
+        }
+        let key = head.to_be_bytes();
+        let value = get_with_prefix(self.storage, &self.prefix, &key);
+        let data = must_deserialize(&value)?;
+        remove_with_prefix(self.storage, &self.prefix, &key);
+        if head + 1 == tail {
+            // queue is empty now, so we can start from the beginning again
+            remove_with_prefix(self.storage, &self.prefix, HEAD_KEY);
+            remove_with_prefix(self.storage, &self.prefix, TAIL_KEY);
+        } else {
+            self.set_pointer(HEAD_KEY, head + 1)?;
+        }
+        Ok(Some(data))
+    }
+
+    /// Returns the first element of the queue without removing it, or None if the queue is empty
+    pub fn peek(&self) -> StdResult<Option<T>> {
+        let head = self.head()?;
+        if head == self.tail()? {
+            return Ok(None);
+        }
+        let value = get_with_prefix(self.storage, &self.prefix, &head.to_be_bytes());
+        must_deserialize(&value).map(Some)
+    }
+
+    fn head(&self) -> StdResult<u64> {
+        self.pointer(HEAD_KEY)
+    }
+
+    fn tail(&self) -> StdResult<u64> {
+        self.pointer(TAIL_KEY)
+    }
+
+    fn pointer(&self, key: &[u8]) -> StdResult<u64> {
+        let value = get_with_prefix(self.storage, &self.prefix, key);
+        Ok(may_deserialize(&value)?.unwrap_or_default())
+    }
+
+    fn set_pointer(&mut self, key: &[u8], value: u64) -> StdResult<()> {
+        set_with_prefix(self.storage, &self.prefix, key, &to_vec(&value)?);
+        Ok(())
+    }
+}
+
+/// ReadonlyQueue provides the read access of a `Queue` without requiring a mutable storage,
+/// e.g. for use in queries
+pub struct ReadonlyQueue<'a, T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    storage: &'a dyn Storage,
+    prefix: Vec<u8>,
+    // see https://doc.rust-lang.org/std/marker/struct.PhantomData.html#unused-type-parameters for why this is needed
+    data: PhantomData<T>,
+}
+
+impl<'a, T> ReadonlyQueue<'a, T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    pub fn new(storage: &'a dyn Storage, namespace: &[u8]) -> Self {
+        ReadonlyQueue {
+            storage,
+            prefix: to_length_prefixed(namespace),
+            data: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements in the queue
+    pub fn len(&self) -> StdResult<u64> {
+        Ok(self.pointer(TAIL_KEY)? - self.pointer(HEAD_KEY)?)
+    }
+
+    pub fn is_empty(&self) -> StdResult<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Returns the first element of the queue, or None if the queue is empty
+    pub fn peek(&self) -> StdResult<Option<T>> {
+        let head = self.pointer(HEAD_KEY)?;
+        if head == self.pointer(TAIL_KEY)? {
+            return Ok(None);
+        }
+        let value = get_with_prefix(self.storage, &self.prefix, &head.to_be_bytes());
+        must_deserialize(&value).map(Some)
+    }
+
+    fn pointer(&self, key: &[u8]) -> StdResult<u64> {
+        let value = get_with_prefix(self.storage, &self.prefix, key);
+        Ok(may_deserialize(&value)?.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+    struct Job {
+        pub id: u32,
+    }
+
+    #[test]
+    fn push_and_pop_in_order() {
+        let mut store = MockStorage::new();
+        let mut jobs = queue::<Job>(&mut store, b"jobs");
+
+        jobs.push_back(&Job { id: 1 }).unwrap();
+        jobs.push_back(&Job { id: 2 }).unwrap();
+        jobs.push_back(&Job { id: 3 }).unwrap();
+        assert_eq!(jobs.len().unwrap(), 3);
+        assert_eq!(jobs.peek().unwrap(), Some(Job { id: 1 }));
+
+        assert_eq!(jobs.pop_front().unwrap(), Some(Job { id: 1 }));
+        assert_eq!(jobs.pop_front().unwrap(), Some(Job { id: 2 }));
+        jobs.push_back(&Job { id: 4 }).unwrap();
+        assert_eq!(jobs.len().unwrap(), 2);
+        assert_eq!(jobs.peek().unwrap(), Some(Job { id: 3 }));
+        assert_eq!(jobs.pop_front().unwrap(), Some(Job { id: 3 }));
+        assert_eq!(jobs.pop_front().unwrap(), Some(Job { id: 4 }));
+        assert_eq!(jobs.pop_front().unwrap(), None);
+    }
+
+    #[test]
+    fn empty_queue() {
+        let mut store = MockStorage::new();
+        let mut jobs = queue::<Job>(&mut store, b"jobs");
+
+        assert_eq!(jobs.len().unwrap(), 0);
+        assert!(jobs.is_empty().unwrap());
+        assert_eq!(jobs.peek().unwrap(), None);
+        assert_eq!(jobs.pop_front().unwrap(), None);
+
+        jobs.push_back(&Job { id: 1 }).unwrap();
+        assert!(!jobs.is_empty().unwrap());
+        jobs.pop_front().unwrap();
+        assert!(jobs.is_empty().unwrap());
+        drop(jobs);
+
+        // popping the last element leaves no data behind
+        #[cfg(feature = "iterator")]
+        assert_eq!(
+            store
+                .range(None, None, cosmwasm_std::Order::Ascending)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn queues_are_independent() {
+        let mut store = MockStorage::new();
+        queue::<Job>(&mut store, b"jobs")
+            .push_back(&Job { id: 1 })
+            .unwrap();
+        queue::<Job>(&mut store, b"other")
+            .push_back(&Job { id: 2 })
+            .unwrap();
+
+        let mut jobs = queue::<Job>(&mut store, b"jobs");
+        assert_eq!(jobs.len().unwrap(), 1);
+        assert_eq!(jobs.pop_front().unwrap(), Some(Job { id: 1 }));
+        assert_eq!(jobs.pop_front().unwrap(), None);
+
+        let mut other = queue::<Job>(&mut store, b"other");
+        assert_eq!(other.pop_front().unwrap(), Some(Job { id: 2 }));
+    }
+
+    #[test]
+    fn readonly_queue_reads_queue() {
+        let mut store = MockStorage::new();
+        let mut jobs = queue::<Job>(&mut store, b"jobs");
+        jobs.push_back(&Job { id: 1 }).unwrap();
+        jobs.push_back(&Job { id: 2 }).unwrap();
+        jobs.pop_front().unwrap();
+
+        let reader = queue_read::<Job>(&store, b"jobs");
+        assert_eq!(reader.len().unwrap(), 1);
+        assert!(!reader.is_empty().unwrap());
+        assert_eq!(reader.peek().unwrap(), Some(Job { id: 2 }));
+
+        let other = queue_read::<Job>(&store, b"other");
+        assert_eq!(other.len().unwrap(), 0);
+        assert!(other.is_empty().unwrap());
+        assert_eq!(other.peek().unwrap(), None);
+    }
+}
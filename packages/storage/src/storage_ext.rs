@@ -0,0 +1,62 @@
+use cosmwasm_std::Storage;
+
+/// StorageExt adds convenience methods for raw bytes to every `Storage` implementation,
+/// including `dyn Storage`.
+pub trait StorageExt {
+    /// Returns the value stored under `key` or an empty vector if it does not exist
+    fn get_or_default(&self, key: &[u8]) -> Vec<u8>;
+
+    /// Stores `value` under `key` unless the key already exists.
+    /// Returns true if the value was written.
+    fn set_if_absent(&mut self, key: &[u8], value: &[u8]) -> bool;
+}
+
+impl<S: Storage + ?Sized> StorageExt for S {
+    fn get_or_default(&self, key: &[u8]) -> Vec<u8> {
+        self.get(key).unwrap_or_default()
+    }
+
+    fn set_if_absent(&mut self, key: &[u8], value: &[u8]) -> bool {
+        if self.get(key).is_some() {
+            return false;
+        }
+        self.set(key, value);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    #[test]
+    fn get_or_default_works() {
+        let mut storage = MockStorage::new();
+        storage.set(b"foo", b"bar");
+
+        assert_eq!(storage.get_or_default(b"foo"), b"bar".to_vec());
+        assert_eq!(storage.get_or_default(b"food"), Vec::<u8>::new());
+
+        // works on trait objects too
+        let storage: &dyn Storage = &storage;
+        assert_eq!(storage.get_or_default(b"foo"), b"bar".to_vec());
+    }
+
+    #[test]
+    fn set_if_absent_works() {
+        let mut storage = MockStorage::new();
+
+        assert!(storage.set_if_absent(b"foo", b"bar"));
+        assert_eq!(storage.get(b"foo"), Some(b"bar".to_vec()));
+
+        assert!(!storage.set_if_absent(b"foo", b"baz"));
+        assert_eq!(storage.get(b"foo"), Some(b"bar".to_vec()));
+
+        // works on trait objects too
+        let storage: &mut dyn Storage = &mut storage;
+        assert!(storage.set_if_absent(b"other", b"value"));
+        assert!(!storage.set_if_absent(b"other", b"changed"));
+        assert_eq!(storage.get(b"other"), Some(b"value".to_vec()));
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::Storage;
+#[cfg(feature = "iterator")]
+use cosmwasm_std::{Order, Pair};
+
+/// TransactionalStorage allows trying out a set of state changes and discarding them later.
+///
+/// All writes go to the underlying storage right away. The original value of every key
+/// is recorded the first time it is written or removed, such that `rollback` can restore
+/// the state from before the transaction (including deleting keys that did not exist).
+/// `commit` keeps all changes. Dropping the transaction without calling either keeps
+/// the changes as well.
+pub struct TransactionalStorage<'a> {
+    storage: &'a mut dyn Storage,
+    originals: HashMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+impl<'a> TransactionalStorage<'a> {
+    pub fn new(storage: &'a mut dyn Storage) -> Self {
+        TransactionalStorage {
+            storage,
+            originals: HashMap::new(),
+        }
+    }
+
+    /// Keeps all changes made through this transaction
+    pub fn commit(self) {}
+
+    /// Restores every key touched by this transaction to its value from before the transaction
+    pub fn rollback(self) {
+        for (key, original) in self.originals {
+            match original {
+                Some(value) => self.storage.set(&key, &value),
+                None => self.storage.remove(&key),
+            }
+        }
+    }
+
+    fn record(&mut self, key: &[u8]) {
+        if !self.originals.contains_key(key) {
+            let original = self.storage.get(key);
+            self.originals.insert(key.to_vec(), original);
+        }
+    }
+}
+
+impl<'a> Storage for TransactionalStorage<'a> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.storage.get(key)
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.record(key);
+        self.storage.set(key, value);
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.record(key);
+        self.storage.remove(key);
+    }
+
+    #[cfg(feature = "iterator")]
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Pair> + 'b> {
+        self.storage.range(start, end, order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    #[test]
+    fn set_then_rollback() {
+        let mut storage = MockStorage::new();
+        storage.set(b"foo", b"bar");
+
+        let mut tx = TransactionalStorage::new(&mut storage);
+        tx.set(b"foo", b"baz");
+        tx.set(b"foo", b"qux");
+        tx.set(b"new", b"value");
+        assert_eq!(tx.get(b"foo"), Some(b"qux".to_vec()));
+        assert_eq!(tx.get(b"new"), Some(b"value".to_vec()));
+        tx.rollback();
+
+        assert_eq!(storage.get(b"foo"), Some(b"bar".to_vec()));
+        assert_eq!(storage.get(b"new"), None);
+    }
+
+    #[test]
+    fn remove_then_rollback() {
+        let mut storage = MockStorage::new();
+        storage.set(b"foo", b"bar");
+
+        let mut tx = TransactionalStorage::new(&mut storage);
+        tx.remove(b"foo");
+        tx.remove(b"missing");
+        assert_eq!(tx.get(b"foo"), None);
+        tx.rollback();
+
+        assert_eq!(storage.get(b"foo"), Some(b"bar".to_vec()));
+        assert_eq!(storage.get(b"missing"), None);
+    }
+
+    #[test]
+    fn commit_keeps_changes() {
+        let mut storage = MockStorage::new();
+        storage.set(b"foo", b"bar");
+        storage.set(b"gone", b"soon");
+
+        let mut tx = TransactionalStorage::new(&mut storage);
+        tx.set(b"foo", b"baz");
+        tx.remove(b"gone");
+        tx.commit();
+
+        assert_eq!(storage.get(b"foo"), Some(b"baz".to_vec()));
+        assert_eq!(storage.get(b"gone"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn range_sees_pending_changes() {
+        let mut storage = MockStorage::new();
+        storage.set(b"a", b"1");
+
+        let mut tx = TransactionalStorage::new(&mut storage);
+        tx.set(b"b", b"2");
+        let all: Vec<Pair> = tx.range(None, None, Order::Ascending).collect();
+        assert_eq!(all.len(), 2);
+        tx.rollback();
+
+        let all: Vec<Pair> = storage.range(None, None, Order::Ascending).collect();
+        assert_eq!(all, vec![(b"a".to_vec(), b"1".to_vec())]);
+    }
+}
@@ -1,5 +1,7 @@
 use serde::de::DeserializeOwned;
 use std::any::type_name;
+#[cfg(feature = "iterator")]
+use std::convert::TryInto;
 
 #[cfg(feature = "iterator")]
 use cosmwasm_std::Pair;
@@ -18,6 +20,24 @@ pub(crate) fn may_deserialize<T: DeserializeOwned>(
     }
 }
 
+/// may_deserialize_or works like may_deserialize, but calls `fallback` with the raw bytes
+/// if they cannot be parsed into `T`. This allows decoding values stored in a legacy format.
+/// Errors returned by the fallback are passed on to the caller.
+pub(crate) fn may_deserialize_or<T, F>(value: &Option<Vec<u8>>, fallback: F) -> StdResult<Option<T>>
+where
+    T: DeserializeOwned,
+    F: FnOnce(&[u8]) -> StdResult<T>,
+{
+    match value {
+        Some(data) => match from_slice(data) {
+            Ok(parsed) => Ok(Some(parsed)),
+            Err(StdError::ParseErr { .. }) => fallback(data).map(Some),
+            Err(err) => Err(err),
+        },
+        None => Ok(None),
+    }
+}
+
 /// must_deserialize parses json bytes from storage (Option), returning NotFound error if no data present
 pub(crate) fn must_deserialize<T: DeserializeOwned>(value: &Option<Vec<u8>>) -> StdResult<T> {
     match value {
@@ -26,17 +46,65 @@ pub(crate) fn must_deserialize<T: DeserializeOwned>(value: &Option<Vec<u8>>) ->
     }
 }
 
+/// deserialize_kv parses the value of a key/value pair from a range under `prefix`. On parse
+/// errors the storage prefix and key are added to the error message (see `with_context`),
+/// such that the broken entry can be found.
 #[cfg(feature = "iterator")]
-pub(crate) fn deserialize_kv<T: DeserializeOwned>(kv: Pair<Vec<u8>>) -> StdResult<Pair<T>> {
+pub(crate) fn deserialize_kv<T: DeserializeOwned>(
+    prefix: &[u8],
+    kv: Pair<Vec<u8>>,
+) -> StdResult<Pair<T>> {
     let (k, v) = kv;
-    let t = from_slice::<T>(&v)?;
+    let t = from_slice::<T>(&v).map_err(|err| with_context(err, prefix, &k))?;
     Ok((k, t))
 }
 
+/// deserialize_u64_kv works like deserialize_kv, but additionally decodes the key
+/// from 8 bytes big endian into a u64
+#[cfg(feature = "iterator")]
+pub(crate) fn deserialize_u64_kv<T: DeserializeOwned>(
+    prefix: &[u8],
+    kv: Pair<Vec<u8>>,
+) -> StdResult<(u64, T)> {
+    let (k, t) = deserialize_kv::<T>(prefix, kv)?;
+    let key: [u8; 8] = k
+        .as_slice()
+        .try_into()
+        .map_err(|_| StdError::invalid_data_size(8, k.len()))?;
+    Ok((u64::from_be_bytes(key), t))
+}
+
+/// with_context adds the hex encoded storage prefix and key to parse and serialization errors,
+/// such that the affected entry can be found. Other errors are returned unchanged.
+pub(crate) fn with_context(err: StdError, prefix: &[u8], key: &[u8]) -> StdError {
+    let context = |msg: String| {
+        format!(
+            "{} (namespace: {}, key: {})",
+            msg,
+            to_hex(prefix),
+            to_hex(key)
+        )
+    };
+    match err {
+        StdError::ParseErr {
+            target_type, msg, ..
+        } => StdError::parse_err(target_type, context(msg)),
+        StdError::SerializeErr {
+            source_type, msg, ..
+        } => StdError::serialize_err(source_type, context(msg)),
+        err => err,
+    }
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cosmwasm_std::{to_vec, StdError};
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::{to_vec, StdError, Storage};
     use serde::{Deserialize, Serialize};
 
     #[derive(Serialize, Deserialize, PartialEq, Debug)]
@@ -63,6 +131,44 @@ mod tests {
         assert_eq!(may_parse, None);
     }
 
+    #[test]
+    fn may_deserialize_or_uses_fallback_on_parse_error() {
+        let person = Person {
+            name: "Maria".to_string(),
+            age: 42,
+        };
+        let value = to_vec(&person).unwrap();
+
+        // fallback not used for proper data
+        let parsed: Option<Person> =
+            may_deserialize_or(&Some(value), |_| panic!("must not be called")).unwrap();
+        assert_eq!(parsed, Some(person));
+
+        // fallback not used for missing data
+        let parsed: Option<Person> =
+            may_deserialize_or(&None, |_| panic!("must not be called")).unwrap();
+        assert_eq!(parsed, None);
+
+        // fallback gets the raw bytes
+        let legacy = b"Maria,42".to_vec();
+        let parsed: Option<Person> = may_deserialize_or(&Some(legacy), |raw| {
+            assert_eq!(raw, b"Maria,42");
+            Ok(Person {
+                name: "Maria".to_string(),
+                age: 42,
+            })
+        })
+        .unwrap();
+        assert_eq!(parsed.unwrap().age, 42);
+
+        // fallback errors are returned
+        let err = may_deserialize_or::<Person, _>(&Some(b"nope".to_vec()), |_| {
+            Err(StdError::generic_err("unknown format"))
+        })
+        .unwrap_err();
+        assert_eq!(err, StdError::generic_err("unknown format"));
+    }
+
     #[test]
     fn must_deserialize_handles_some() {
         let person = Person {
@@ -76,6 +182,60 @@ mod tests {
         assert_eq!(parsed, person);
     }
 
+    #[test]
+    fn must_deserialize_handles_parse_error() {
+        let mut storage = MockStorage::new();
+        storage.set(b"person", b"{\"name\":\"Maria\"");
+        let loaded = storage.get(b"person");
+
+        match must_deserialize::<Person>(&loaded).unwrap_err() {
+            StdError::ParseErr { target_type, .. } => {
+                assert_eq!(target_type, "cosmwasm_storage::type_helpers::tests::Person")
+            }
+            e => panic!("Unexpected error {}", e),
+        }
+
+        // may_deserialize reports the same error rather than Ok(None)
+        match may_deserialize::<Person>(&loaded).unwrap_err() {
+            StdError::ParseErr { .. } => {}
+            e => panic!("Unexpected error {}", e),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn deserialize_kv_works() {
+        let person = Person {
+            name: "Maria".to_string(),
+            age: 42,
+        };
+        let kv = (b"maria".to_vec(), to_vec(&person).unwrap());
+
+        let (key, parsed): Pair<Person> = deserialize_kv(b"\x00\x06people", kv).unwrap();
+        assert_eq!(key, b"maria");
+        assert_eq!(parsed, person);
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn deserialize_kv_adds_prefix_and_key_to_parse_error() {
+        let kv = (b"\x00\x01bad".to_vec(), b"{\"name\":".to_vec());
+
+        match deserialize_kv::<Person>(b"\x00\x02ab", kv).unwrap_err() {
+            StdError::ParseErr {
+                target_type, msg, ..
+            } => {
+                assert_eq!(target_type, "cosmwasm_storage::type_helpers::tests::Person");
+                assert!(
+                    msg.ends_with(" (namespace: 00026162, key: 0001626164)"),
+                    "got: {}",
+                    msg
+                );
+            }
+            e => panic!("Unexpected error {}", e),
+        }
+    }
+
     #[test]
     fn must_deserialize_handles_none() {
         let parsed = must_deserialize::<Person>(&None);